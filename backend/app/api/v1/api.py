@@ -5,7 +5,7 @@ API v1 路由汇总模块
 """
 from fastapi import APIRouter
 
-from app.api.v1.endpoints import documents, chat, settings
+from app.api.v1.endpoints import documents, chat, settings, tasks, warmup, device
 
 # 创建 v1 版本的主路由器
 api_router = APIRouter()
@@ -14,3 +14,6 @@ api_router = APIRouter()
 api_router.include_router(documents.router)
 api_router.include_router(chat.router)
 api_router.include_router(settings.router)
+api_router.include_router(tasks.router)
+api_router.include_router(warmup.router)
+api_router.include_router(device.router)
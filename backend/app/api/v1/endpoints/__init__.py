@@ -3,6 +3,6 @@ API v1 端点模块
 
 导出所有端点路由。
 """
-from app.api.v1.endpoints import documents, chat
+from app.api.v1.endpoints import documents, chat, tasks, warmup, device
 
-__all__ = ["documents", "chat"]
+__all__ = ["documents", "chat", "tasks", "warmup", "device"]
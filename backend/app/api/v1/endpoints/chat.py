@@ -75,7 +75,7 @@ async def chat_stream(
     返回 SSE 格式的流式响应：
     - data: {"type": "chunk", "content": "..."}  - 内容片段
     - data: {"type": "sources", "sources": [...]} - 来源信息
-    - data: {"type": "done"} - 完成标记
+    - data: {"type": "done", "usage": {...}, "timing": {...}} - 完成标记（token 用量和各阶段耗时）
     """
     try:
         return StreamingResponse(
@@ -0,0 +1,31 @@
+"""
+计算设备 API 端点模块
+
+提供嵌入模型实际使用的计算设备，桌面端据此提示 GPU 是否生效。
+"""
+from typing import Optional
+
+from fastapi import APIRouter
+from pydantic import BaseModel, Field
+
+# 创建路由器
+router = APIRouter(tags=["计算设备"])
+
+
+class DeviceResponse(BaseModel):
+    """计算设备响应模型"""
+    device: str = Field(..., description="嵌入模型使用的设备：cpu、cuda，或 remote（远程接口）")
+    reason: Optional[str] = Field(None, description="请求了 GPU 却未能使用时的原因")
+
+
+@router.get(
+    "/device",
+    response_model=DeviceResponse,
+    summary="获取计算设备",
+    description="返回嵌入模型实际使用的计算设备（由 DEVICE 配置请求）"
+)
+def get_device() -> DeviceResponse:
+    """获取计算设备（同步执行，FastAPI 会放到线程池中运行）"""
+    from app.services.vector_service import vector_service
+
+    return DeviceResponse(**vector_service.device_info())
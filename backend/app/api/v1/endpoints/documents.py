@@ -4,12 +4,15 @@
 提供文档上传、列表、删除等接口。
 """
 import os
-from typing import List
+import threading
+from contextlib import contextmanager
+from typing import List, Optional
 
 from fastapi import APIRouter, BackgroundTasks, Depends, File, HTTPException, UploadFile, status
 from fastapi.responses import FileResponse, Response
 from sqlalchemy.orm import Session
 
+from app.core.config import DATA_DIR, settings
 from app.crud import document as document_crud
 from app.db.session import get_db
 from app.schemas.document import (
@@ -21,10 +24,44 @@ from app.schemas.document import (
 from app.services.doc_service import document_service
 from app.services.vector_service import vector_service
 from app.services.thumbnail_service import thumbnail_service
+from app.services.task_service import task_service, TaskCancelled
 
 # 创建路由器
 router = APIRouter(prefix="/documents", tags=["文档管理"])
 
+# 限制同时在后台处理的文档数量，其余文档的任务保持排队状态
+_ingest_slots = threading.BoundedSemaphore(max(1, settings.INGEST_CONCURRENCY))
+
+# 索引进行期间在数据目录中保留的锁文件，内容为后端进程 pid，桌面端据此判断是否有残留的锁
+INDEX_LOCK_FILE = DATA_DIR / "index.lock"
+_index_lock_guard = threading.Lock()
+_index_lock_holders = 0
+
+
+@contextmanager
+def _index_lock():
+    """
+    在索引文档期间持有 index.lock
+    
+    多个文档同时处理时共用同一个锁文件：第一个开始的任务创建它，最后一个结束的任务删除它。
+    """
+    global _index_lock_holders
+    with _index_lock_guard:
+        if _index_lock_holders == 0:
+            INDEX_LOCK_FILE.parent.mkdir(parents=True, exist_ok=True)
+            INDEX_LOCK_FILE.write_text(str(os.getpid()))
+        _index_lock_holders += 1
+    try:
+        yield
+    finally:
+        with _index_lock_guard:
+            _index_lock_holders -= 1
+            if _index_lock_holders == 0:
+                try:
+                    INDEX_LOCK_FILE.unlink()
+                except FileNotFoundError:
+                    pass
+
 
 @router.post(
     "/upload",
@@ -62,6 +99,14 @@ async def upload_document(
             detail=error_msg
         )
     
+    # 所有上传（界面上传和桌面端导入）都经过这里，在此统一检查文档库大小上限
+    within_quota, error_msg = document_service.check_vault_quota(file_size)
+    if not within_quota:
+        raise HTTPException(
+            status_code=status.HTTP_507_INSUFFICIENT_STORAGE,
+            detail=error_msg
+        )
+    
     # 保存文件
     try:
         filepath = document_service.save_file(file.filename, content)
@@ -91,24 +136,60 @@ async def upload_document(
         message="文件上传成功，正在后台处理..."
     )
     
+    # 安全模式下不自动建立索引，文档保持 pending 状态
+    if settings.SAFE_MODE:
+        response.message = "安全模式：文件已上传，未自动建立索引"
+        return response
+    
     # 使用 BackgroundTasks 在后台处理文档（不阻塞响应）
+    task_id = task_service.create(
+        "ingest", f"处理文档 {db_document.filename}", db_document.id
+    )
     background_tasks.add_task(
-        process_document_background, db_document.id, filepath
+        process_document_background, db_document.id, filepath, task_id
     )
     
     return response
 
 
-def process_document_background(doc_id: int, filepath: str):
+def _doc_log(doc_id: int, message: str) -> None:
+    """
+    输出带文档标记的处理日志
+    
+    桌面端按 [doc:ID] 前缀把日志归到对应文档，便于查看单个文档的处理记录。
+    """
+    print(f"[doc:{doc_id}] {message}", flush=True)
+
+
+def process_document_background(doc_id: int, filepath: str, task_id: Optional[str] = None):
     """
     后台处理文档（在线程池中执行，避免阻塞事件循环）
     
-    包括：文本提取、切片、向量化、生成缩略图
+    同时处理的文档数量不超过 INGEST_CONCURRENCY，超出的文档等待空闲名额。
+    处理期间持有 index.lock。
+    """
+    with _ingest_slots, _index_lock():
+        _process_document(doc_id, filepath, task_id)
+
+
+def _process_document(doc_id: int, filepath: str, task_id: Optional[str] = None):
+    """
+    处理一个文档
+    
+    包括：文本提取、切片、向量化、生成缩略图。
+    关联了后台任务时，在各阶段之间检查任务是否已被取消。
     """
     from app.db.session import SessionLocal
     db = SessionLocal()
     
+    def checkpoint():
+        if task_id is not None:
+            task_service.check_cancelled(task_id)
+    
     try:
+        if task_id is not None:
+            task_service.update(task_id, "running")
+        _doc_log(doc_id, f"开始处理 {os.path.basename(filepath)}")
         # 更新状态为处理中
         document_crud.update_document(
             db, doc_id, 
@@ -116,7 +197,9 @@ def process_document_background(doc_id: int, filepath: str):
         )
         
         # 提取文本（同步操作，但在线程池中执行不会阻塞主线程）
+        checkpoint()
         text, page_count = document_service.extract_text(filepath)
+        _doc_log(doc_id, f"文本提取完成，共 {page_count} 页，{len(text)} 个字符")
         
         if not text.strip():
             raise ValueError("无法从文档中提取文本内容")
@@ -126,14 +209,19 @@ def process_document_background(doc_id: int, filepath: str):
         
         if not chunks:
             raise ValueError("文本切片失败，未生成任何切片")
+        _doc_log(doc_id, f"文本切片完成，共 {len(chunks)} 个切片")
+        checkpoint()
+        # 开始写入向量数据库后不再允许取消，避免留下不完整的索引
+        if task_id is not None:
+            task_service.update(task_id, "running", cancellable=False)
         
         # 添加到向量数据库
         try:
             vector_service.add_documents(doc_id, chunks)
-            print(f"✓ 文档 {doc_id} 处理完成，共 {len(chunks)} 个切片")
+            _doc_log(doc_id, f"✓ 处理完成，共 {len(chunks)} 个切片")
         except Exception as e:
             # 向量化失败但继续
-            print(f"⚠ 文档 {doc_id} 向量化失败: {e}")
+            _doc_log(doc_id, f"⚠ 向量化失败: {e}")
         
         # 更新文档状态为已处理
         document_crud.update_document(
@@ -144,12 +232,30 @@ def process_document_background(doc_id: int, filepath: str):
         # 生成缩略图（同步操作）
         try:
             thumbnail_service.get_or_generate_thumbnail(doc_id, filepath)
-            print(f"✓ 文档 {doc_id} 缩略图生成成功")
+            _doc_log(doc_id, "✓ 缩略图生成成功")
         except Exception as e:
-            print(f"⚠ 文档 {doc_id} 缩略图生成失败: {e}")
+            _doc_log(doc_id, f"⚠ 缩略图生成失败: {e}")
+        
+        if task_id is not None:
+            task_service.update(task_id, "completed")
         
+    except TaskCancelled:
+        _doc_log(doc_id, "处理已取消")
+        task_service.finish_cancelled(task_id)
+        try:
+            document_crud.update_document(
+                db, doc_id,
+                DocumentUpdate(status="failed", error_message="处理已取消")
+            )
+        except Exception:
+            pass
     except Exception as e:
-        print(f"✗ 文档 {doc_id} 后台处理异常: {e}")
+        _doc_log(doc_id, f"✗ 后台处理异常: {e}")
+        if task_id is not None:
+            try:
+                task_service.update(task_id, "failed")
+            except TaskCancelled:
+                task_service.finish_cancelled(task_id)
         try:
             document_crud.update_document(
                 db, doc_id,
@@ -5,7 +5,8 @@
 """
 import json
 import os
-from typing import Optional
+import time
+from typing import List, Optional
 
 from fastapi import APIRouter, HTTPException, status
 from pydantic import BaseModel, Field
@@ -27,6 +28,18 @@ class LLMConfigRequest(BaseModel):
     embedding_model: Optional[str] = Field(None, description="嵌入模型名称")
 
 
+class EmbeddingBenchmarkRequest(BaseModel):
+    """嵌入速度测试请求模型"""
+    texts: List[str] = Field(..., min_length=1, max_length=1024, description="一批待向量化的文本")
+
+
+class EmbeddingBenchmarkResponse(BaseModel):
+    """嵌入速度测试响应模型"""
+    count: int = Field(..., description="向量化的文本数量")
+    dimensions: int = Field(..., description="向量维度")
+    elapsed_ms: float = Field(..., description="向量化耗时（毫秒）")
+
+
 class LLMConfigResponse(BaseModel):
     """LLM 配置响应模型"""
     api_base: Optional[str] = Field(None, description="API 基础URL")
@@ -184,3 +197,30 @@ async def test_llm_connection():
             "success": False,
             "message": f"连接失败: {str(e)}",
         }
+
+
+@router.post(
+    "/embedding/benchmark",
+    response_model=EmbeddingBenchmarkResponse,
+    summary="测试嵌入速度",
+    description="用当前嵌入模型向量化一批文本并返回耗时，结果不写入向量数据库"
+)
+def benchmark_embedding(request: EmbeddingBenchmarkRequest) -> EmbeddingBenchmarkResponse:
+    """测试嵌入速度（同步执行，FastAPI 会放到线程池中运行）"""
+    from app.services.vector_service import vector_service
+    
+    started = time.perf_counter()
+    try:
+        vectors = vector_service.embed_texts(request.texts)
+    except Exception as e:
+        raise HTTPException(
+            status_code=status.HTTP_500_INTERNAL_SERVER_ERROR,
+            detail=f"向量化失败: {str(e)}"
+        )
+    elapsed_ms = (time.perf_counter() - started) * 1000
+    
+    return EmbeddingBenchmarkResponse(
+        count=len(vectors),
+        dimensions=len(vectors[0]) if vectors else 0,
+        elapsed_ms=elapsed_ms,
+    )
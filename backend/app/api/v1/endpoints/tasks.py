@@ -0,0 +1,57 @@
+"""
+后台任务 API 端点模块
+
+提供后台任务的查询和取消接口。
+"""
+from typing import List, Optional
+
+from fastapi import APIRouter, HTTPException, status
+from pydantic import BaseModel, Field
+
+from app.services.task_service import task_service
+
+# 创建路由器
+router = APIRouter(prefix="/tasks", tags=["后台任务"])
+
+
+class TaskInfo(BaseModel):
+    """后台任务信息模型"""
+    id: str = Field(..., description="任务 ID")
+    kind: str = Field(..., description="任务类型")
+    description: str = Field(..., description="任务说明")
+    document_id: Optional[int] = Field(None, description="关联的文档 ID")
+    status: str = Field(..., description="任务状态：queued 或 running")
+    cancellable: bool = Field(..., description="是否可以取消")
+    created_at: float = Field(..., description="创建时间（Unix 时间戳，秒）")
+
+
+@router.get(
+    "",
+    response_model=List[TaskInfo],
+    summary="获取后台任务",
+    description="获取排队中和正在运行的后台任务"
+)
+async def list_tasks() -> List[TaskInfo]:
+    """获取后台任务列表"""
+    return [TaskInfo(**task) for task in task_service.list()]
+
+
+@router.post(
+    "/{task_id}/cancel",
+    summary="取消后台任务",
+    description="取消一个可取消的后台任务，正在运行的任务会在当前阶段结束后停止"
+)
+async def cancel_task(task_id: str):
+    """取消后台任务"""
+    result = task_service.cancel(task_id)
+    if result is None:
+        raise HTTPException(
+            status_code=status.HTTP_404_NOT_FOUND,
+            detail=f"任务 {task_id} 不存在或已结束"
+        )
+    if not result:
+        raise HTTPException(
+            status_code=status.HTTP_409_CONFLICT,
+            detail=f"任务 {task_id} 当前不可取消"
+        )
+    return {"id": task_id, "cancelled": True}
@@ -0,0 +1,39 @@
+"""
+模型预热 API 端点模块
+
+在第一次提问前加载向量数据库和嵌入模型，不调用 LLM，也不写入问答历史。
+"""
+import time
+
+from fastapi import APIRouter, HTTPException, status
+from pydantic import BaseModel, Field
+
+# 创建路由器
+router = APIRouter(tags=["模型预热"])
+
+
+class WarmupResponse(BaseModel):
+    """预热结果模型"""
+    elapsed_ms: float = Field(..., description="预热耗时（毫秒）")
+
+
+@router.post(
+    "/warmup",
+    response_model=WarmupResponse,
+    summary="预热模型",
+    description="打开文档集合并向量化一条短文本，让嵌入模型在第一次提问前完成加载"
+)
+def warmup() -> WarmupResponse:
+    """预热模型（同步执行，FastAPI 会放到线程池中运行）"""
+    from app.services.vector_service import vector_service
+
+    started = time.perf_counter()
+    try:
+        _ = vector_service.collection
+        vector_service.embed_texts(["warmup"])
+    except Exception as e:
+        raise HTTPException(
+            status_code=status.HTTP_500_INTERNAL_SERVER_ERROR,
+            detail=f"预热失败: {str(e)}"
+        )
+    return WarmupResponse(elapsed_ms=(time.perf_counter() - started) * 1000)
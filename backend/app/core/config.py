@@ -26,7 +26,8 @@ else:
     # 开发环境：使用项目目录
     BACKEND_DIR = Path(__file__).parent.parent.parent.resolve()
 
-DATA_DIR = BACKEND_DIR / "data"
+# 桌面端通过 DATA_DIR 环境变量指定数据目录（当前文档库），未指定时使用默认位置
+DATA_DIR = Path(os.environ["DATA_DIR"]) if os.environ.get("DATA_DIR") else BACKEND_DIR / "data"
 
 
 class Settings(BaseSettings):
@@ -56,6 +57,8 @@ class Settings(BaseSettings):
     OPENAI_API_BASE: Optional[str] = Field(default=None, description="OpenAI API 基础URL")
     LLM_MODEL: str = Field(default="qwen3-max-preview", description="使用的LLM模型名称")
     EMBEDDING_MODEL: str = Field(default="Qwen/Qwen3-Embedding-8B", description="嵌入模型名称")
+    EMBEDDING_PROVIDER: str = Field(default="openai", description="嵌入模型来源：local 使用本地模型，openai 使用 OpenAI 兼容接口")
+    DEVICE: str = Field(default="cpu", description="本地嵌入模型使用的计算设备：cpu 或 cuda")
     
     # 向量数据库配置
     CHROMA_PERSIST_DIRECTORY: str = Field(
@@ -70,6 +73,7 @@ class Settings(BaseSettings):
     # 文件上传配置
     UPLOAD_DIR: str = Field(default=str(DATA_DIR / "uploads"), description="文件上传目录")
     MAX_UPLOAD_SIZE: int = Field(default=10 * 1024 * 1024, description="最大上传文件大小(字节)")  # 10MB
+    MAX_VAULT_BYTES: Optional[int] = Field(default=None, description="文档库大小上限(字节)，未设置时不限制")
     ALLOWED_EXTENSIONS: List[str] = Field(
         default=["pdf", "txt", "docx"], 
         description="允许上传的文件扩展名"
@@ -79,9 +83,19 @@ class Settings(BaseSettings):
     CHUNK_SIZE: int = Field(default=1000, description="文本切片大小")
     CHUNK_OVERLAP: int = Field(default=200, description="文本切片重叠大小")
     
+    # 索引性能配置
+    EMBEDDING_BATCH_SIZE: int = Field(default=32, description="每次向量化并写入向量数据库的切片数量")
+    INGEST_CONCURRENCY: int = Field(default=2, description="同时在后台处理的文档数量")
+    
     # 检索配置
     TOP_K_RESULTS: int = Field(default=5, description="检索返回的Top-K结果数量")
     
+    # 模型缓存目录：嵌入模型、分词器等下载的文件都放在这里，未设置时使用各个库的默认位置
+    CACHE_DIR: Optional[str] = Field(default=None, description="模型文件缓存目录")
+    
+    # 安全模式：上传的文档不自动建立索引，便于排查导致崩溃的文档
+    SAFE_MODE: bool = Field(default=False, description="是否以安全模式运行")
+    
     # CORS 配置
     CORS_ORIGINS: List[str] = Field(
         default=[
@@ -110,3 +124,29 @@ class Settings(BaseSettings):
 
 # 创建全局配置实例
 settings = Settings()
+
+
+def apply_cache_dir() -> None:
+    """
+    把各个库的模型缓存指向 CACHE_DIR
+    
+    HuggingFace、sentence-transformers 和 tiktoken 通过环境变量确定缓存位置（已显式设置的不覆盖）；
+    ChromaDB 默认嵌入模型的下载位置固定在主目录下，需要直接修改。
+    """
+    if not settings.CACHE_DIR:
+        return
+    cache_dir = Path(settings.CACHE_DIR)
+    os.environ.setdefault("XDG_CACHE_HOME", str(cache_dir))
+    os.environ.setdefault("HF_HOME", str(cache_dir / "huggingface"))
+    os.environ.setdefault("SENTENCE_TRANSFORMERS_HOME", str(cache_dir / "sentence_transformers"))
+    os.environ.setdefault("TIKTOKEN_CACHE_DIR", str(cache_dir / "tiktoken"))
+    try:
+        from chromadb.utils.embedding_functions import ONNXMiniLM_L6_V2
+        ONNXMiniLM_L6_V2.DOWNLOAD_PATH = (
+            cache_dir / "chroma" / "onnx_models" / ONNXMiniLM_L6_V2.MODEL_NAME
+        )
+    except ImportError:
+        pass
+
+
+apply_cache_dir()
@@ -128,6 +128,31 @@ async def rate_limit_middleware(request: Request, call_next: Callable):
     return await call_next(request)
 
 
+@app.middleware("http")
+async def request_trace_middleware(request: Request, call_next: Callable):
+    """
+    请求追踪中间件
+    
+    桌面端为每个请求生成 X-Request-Id 并在响应中原样返回；
+    开启请求追踪时（X-Request-Trace: 1）输出带请求 ID 的开始和结束日志，
+    便于把界面上的操作与后端的处理对应起来。
+    """
+    request_id = request.headers.get("x-request-id")
+    tracing = bool(request_id) and request.headers.get("x-request-trace") == "1"
+    started = time.perf_counter()
+    if tracing:
+        print(f"[req:{request_id}] 开始 {request.method} {request.url.path}", flush=True)
+    
+    response = await call_next(request)
+    
+    if request_id:
+        response.headers["X-Request-Id"] = request_id
+    if tracing:
+        elapsed_ms = (time.perf_counter() - started) * 1000
+        print(f"[req:{request_id}] 完成 {response.status_code}，耗时 {elapsed_ms:.0f} ms", flush=True)
+    return response
+
+
 # 自定义业务异常类
 class BusinessException(Exception):
     """业务异常基类"""
@@ -225,6 +250,32 @@ async def health_check():
     }
 
 
+# 接口版本（语义化版本号）：新增可选接口时增加次版本号，不兼容的修改增加主版本号
+API_VERSION = "1.3.0"
+
+# 当前后端支持的可选功能，桌面端据此决定是否显示对应的操作
+API_FEATURES = [
+    "streaming",
+    "task_cancellation",
+    "request_tracing",
+    "embedding_benchmark",
+    "document_thumbnails",
+]
+
+
+@app.get("/api-version", tags=["健康检查"])
+async def api_version():
+    """
+    接口版本接口
+    
+    返回接口版本号和支持的可选功能列表，旧版本客户端可以据此隐藏不支持的功能。
+    """
+    return {
+        "version": API_VERSION,
+        "features": API_FEATURES
+    }
+
+
 # 直接运行时启动服务器（支持右键直接运行 + 热更新）
 if __name__ == "__main__":
     import signal
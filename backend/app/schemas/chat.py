@@ -4,7 +4,7 @@
 定义聊天 API 请求和响应的数据结构。
 """
 from datetime import datetime
-from typing import List, Optional
+from typing import Dict, List, Optional
 
 from pydantic import BaseModel, Field
 
@@ -78,6 +78,7 @@ class ChatResponse(BaseModel):
     session_id: Optional[str] = Field(None, description="会话ID")
     query: Optional[str] = Field(None, description="原始问题")
     usage: Optional[TokenUsage] = Field(None, description="Token 用量信息")
+    timing: Optional[Dict[str, int]] = Field(None, description="各阶段耗时（毫秒）：retrieval_ms、generation_ms")
 
 
 class ChatMessageBase(BaseModel):
@@ -6,6 +6,7 @@
 from app.services.doc_service import document_service, DocumentService
 from app.services.vector_service import vector_service, VectorService
 from app.services.chat_service import chat_service, ChatService
+from app.services.task_service import task_service, TaskService
 
 __all__ = [
     "document_service",
@@ -14,4 +15,6 @@ __all__ = [
     "VectorService",
     "chat_service",
     "ChatService",
+    "task_service",
+    "TaskService",
 ]
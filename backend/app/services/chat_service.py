@@ -5,6 +5,7 @@
 """
 import json
 import re
+import time
 import uuid
 from typing import AsyncGenerator, Dict, List, Optional, Tuple
 
@@ -359,6 +360,7 @@ class ChatService:
         doc_id = doc_ids[0] if doc_ids else None
         top_k = request.top_k or settings.TOP_K_RESULTS
         
+        retrieval_started = time.perf_counter()
         search_results = vector_service.search(
             query=question,
             doc_id=doc_id,
@@ -379,9 +381,13 @@ class ChatService:
         # 组合系统提示词、上下文和用户问题
         messages = self._build_prompt(context, question)
         
+        retrieval_ms = int((time.perf_counter() - retrieval_started) * 1000)
+        
         # --- 步骤 3: 生成 (Generate) ---
         # 调用 LLM 获取回答
+        generation_started = time.perf_counter()
         answer, usage = await self._call_llm(messages)
+        generation_ms = int((time.perf_counter() - generation_started) * 1000)
         
         # --- 步骤 4: 记录 ---
         # 保存用户消息到数据库
@@ -412,7 +418,8 @@ class ChatService:
             sources=sources,
             session_id=session_id,
             query=question,
-            usage=usage
+            usage=usage,
+            timing={"retrieval_ms": retrieval_ms, "generation_ms": generation_ms}
         )
     
     async def chat_stream(
@@ -445,6 +452,7 @@ class ChatService:
         top_k = request.top_k or settings.TOP_K_RESULTS
         
         # --- 步骤 1: 检索 (Retrieve) ---
+        retrieval_started = time.perf_counter()
         search_results = vector_service.search(
             query=question,
             doc_id=doc_id,
@@ -459,6 +467,7 @@ class ChatService:
         
         context, sources = self._build_context(search_results, first_page_content)
         messages = self._build_prompt(context, question)
+        retrieval_ms = int((time.perf_counter() - retrieval_started) * 1000)
         
         # 发送来源信息
         sources_data = [s.model_dump(by_alias=True) for s in sources]
@@ -467,12 +476,14 @@ class ChatService:
         # --- 步骤 3: 流式生成 (Generate) ---
         full_answer = ""
         final_usage = None
+        generation_started = time.perf_counter()
         async for chunk, usage in self._call_llm_stream(messages):
             if chunk:
                 full_answer += chunk
                 yield f"data: {json.dumps({'type': 'chunk', 'content': chunk}, ensure_ascii=False)}\n\n"
             if usage:
                 final_usage = usage
+        generation_ms = int((time.perf_counter() - generation_started) * 1000)
         
         # --- 步骤 4: 记录 ---
         # 保存用户消息到数据库
@@ -498,8 +509,11 @@ class ChatService:
             )
         )
         
-        # 发送完成标记（包含 token 用量）
-        done_data = {'type': 'done'}
+        # 发送完成标记（包含 token 用量和各阶段耗时）
+        done_data = {
+            'type': 'done',
+            'timing': {'retrieval_ms': retrieval_ms, 'generation_ms': generation_ms}
+        }
         if final_usage:
             done_data['usage'] = final_usage
         yield f"data: {json.dumps(done_data, ensure_ascii=False)}\n\n"
@@ -12,7 +12,7 @@ from pypdf import PdfReader
 from docx import Document as DocxDocument
 from sqlalchemy.orm import Session
 
-from app.core.config import settings
+from app.core.config import settings, DATA_DIR
 from app.crud import document as document_crud
 from app.models.document import Document
 from app.schemas.document import DocumentCreate, DocumentUpdate
@@ -81,6 +81,37 @@ class DocumentService:
         
         return True, ""
     
+    def check_vault_quota(self, file_size: int) -> Tuple[bool, str]:
+        """
+        检查保存文件后文档库是否会超出大小上限（MAX_VAULT_BYTES）
+        
+        文档库占用按数据目录下所有文件的实际大小统计，与桌面端的统计方式一致。
+        
+        Args:
+            file_size: 待保存文件的大小(字节)
+            
+        Returns:
+            Tuple[bool, str]: (是否允许保存, 错误消息)
+        """
+        if settings.MAX_VAULT_BYTES is None:
+            return True, ""
+        
+        used = 0
+        for root, _, files in os.walk(DATA_DIR):
+            for name in files:
+                try:
+                    used += os.path.getsize(os.path.join(root, name))
+                except OSError:
+                    continue
+        
+        if used + file_size > settings.MAX_VAULT_BYTES:
+            # 与桌面端相同的 VaultFull 前缀，前端据此提示用户清理或调高上限
+            return False, (
+                f"VaultFull: 文档库空间不足（已用 {used} 字节，还需 {file_size} 字节，"
+                f"上限 {settings.MAX_VAULT_BYTES} 字节）"
+            )
+        return True, ""
+    
     def save_file(self, filename: str, content: bytes) -> str:
         """
         保存上传的文件到磁盘
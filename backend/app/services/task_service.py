@@ -0,0 +1,136 @@
+"""
+后台任务服务模块
+
+记录文档处理等后台任务的状态，支持查询和取消尚未完成的任务。
+"""
+import json
+import threading
+import time
+from typing import Dict, List, Optional
+
+
+class TaskCancelled(Exception):
+    """任务已被取消"""
+    pass
+
+
+class TaskService:
+    """
+    后台任务服务类
+    
+    任务完成后从列表中移除；状态变化时输出 [task] 开头的 JSON 日志，
+    桌面端据此推送任务状态更新。
+    """
+    
+    def __init__(self):
+        """初始化任务列表"""
+        self._lock = threading.Lock()
+        self._tasks: Dict[str, dict] = {}
+        self._next_id = 1
+    
+    def _emit(self, task: dict) -> None:
+        """输出任务状态变化"""
+        print(f"[task] {json.dumps(task, ensure_ascii=False)}", flush=True)
+    
+    def create(self, kind: str, description: str, document_id: Optional[int] = None) -> str:
+        """
+        登记一个排队中的任务
+        
+        Args:
+            kind: 任务类型，例如 ingest
+            description: 任务说明
+            document_id: 关联的文档 ID（可选）
+            
+        Returns:
+            str: 任务 ID
+        """
+        with self._lock:
+            task_id = str(self._next_id)
+            self._next_id += 1
+            task = {
+                "id": task_id,
+                "kind": kind,
+                "description": description,
+                "document_id": document_id,
+                "status": "queued",
+                "cancellable": True,
+                "created_at": time.time(),
+            }
+            self._tasks[task_id] = task
+            snapshot = dict(task)
+        self._emit(snapshot)
+        return task_id
+    
+    def update(self, task_id: str, status: str, cancellable: Optional[bool] = None) -> None:
+        """
+        更新任务状态；任务已被取消时抛出 TaskCancelled
+        
+        Args:
+            task_id: 任务 ID
+            status: 新状态（running、completed、failed）
+            cancellable: 是否还可以取消（可选）
+        """
+        with self._lock:
+            task = self._tasks.get(task_id)
+            if task is None:
+                return
+            if task.get("cancel_requested"):
+                raise TaskCancelled()
+            task["status"] = status
+            if cancellable is not None:
+                task["cancellable"] = cancellable
+            if status in ("completed", "failed", "cancelled"):
+                self._tasks.pop(task_id, None)
+            snapshot = {k: v for k, v in task.items() if k != "cancel_requested"}
+        self._emit(snapshot)
+    
+    def check_cancelled(self, task_id: str) -> None:
+        """任务已被取消时抛出 TaskCancelled，在处理的各个阶段之间调用"""
+        with self._lock:
+            task = self._tasks.get(task_id)
+            cancelled = task is not None and task.get("cancel_requested", False)
+        if cancelled:
+            raise TaskCancelled()
+    
+    def finish_cancelled(self, task_id: str) -> None:
+        """任务响应取消请求后调用，从列表中移除"""
+        with self._lock:
+            task = self._tasks.pop(task_id, None)
+            if task is None:
+                return
+            task["status"] = "cancelled"
+            task["cancellable"] = False
+            snapshot = {k: v for k, v in task.items() if k != "cancel_requested"}
+        self._emit(snapshot)
+    
+    def list(self) -> List[dict]:
+        """返回排队中和正在运行的任务"""
+        with self._lock:
+            return [
+                {k: v for k, v in task.items() if k != "cancel_requested"}
+                for task in self._tasks.values()
+            ]
+    
+    def cancel(self, task_id: str) -> Optional[bool]:
+        """
+        请求取消任务
+        
+        Returns:
+            Optional[bool]: 任务不存在时为 None，不可取消时为 False
+        """
+        with self._lock:
+            task = self._tasks.get(task_id)
+            if task is None:
+                return None
+            if not task["cancellable"]:
+                return False
+            task["cancel_requested"] = True
+            task["cancellable"] = False
+            snapshot = {k: v for k, v in task.items() if k != "cancel_requested"}
+            snapshot["status"] = "cancelling"
+        self._emit(snapshot)
+        return True
+
+
+# 创建全局任务服务实例
+task_service = TaskService()
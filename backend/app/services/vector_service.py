@@ -5,7 +5,7 @@
 支持使用 OpenAI Embedding 或 ChromaDB 默认嵌入。
 """
 import os
-from typing import List, Optional
+from typing import List, Optional, Tuple
 
 import chromadb
 from chromadb.config import Settings as ChromaSettings
@@ -26,6 +26,8 @@ class VectorService:
         self._client: Optional[chromadb.ClientAPI] = None
         self._collection = None
         self._embedding_function = None
+        # 嵌入模型实际使用的计算设备和（未能使用 GPU 时的）原因
+        self._device: Tuple[str, Optional[str]] = ("cpu", None)
         self._ensure_persist_dir()
     
     def _ensure_persist_dir(self) -> None:
@@ -45,7 +47,10 @@ class VectorService:
             config = get_effective_config()
             api_key = config.get("api_key")
             
-            if api_key and api_key != "your_api_key_here":
+            if settings.EMBEDDING_PROVIDER == "local":
+                print("✓ 已配置使用本地嵌入模型")
+                self._embedding_function = self._local_embedding_function()
+            elif api_key and api_key != "your_api_key_here":
                 try:
                     from chromadb.utils.embedding_functions import OpenAIEmbeddingFunction
                     self._embedding_function = OpenAIEmbeddingFunction(
@@ -53,14 +58,63 @@ class VectorService:
                         api_base=config.get("api_base"),
                         model_name=config.get("embedding_model", "text-embedding-ada-002")
                     )
+                    self._device = ("remote", "嵌入由 OpenAI 兼容接口计算")
                     print(f"✓ 使用 OpenAI Embedding 模型: {config.get('embedding_model')}")
                 except Exception as e:
                     print(f"⚠ OpenAI Embedding 初始化失败，使用默认嵌入: {e}")
-                    self._embedding_function = None
+                    self._embedding_function = self._local_embedding_function()
             else:
                 print("⚠ 未配置 OpenAI API Key，使用 ChromaDB 默认嵌入模型")
+                self._embedding_function = self._local_embedding_function()
         return self._embedding_function
     
+    def _resolve_device(self) -> Tuple[str, Optional[str]]:
+        """
+        按 DEVICE 配置确定本地嵌入模型使用的设备
+        
+        Returns:
+            Tuple[str, Optional[str]]: (设备名称, 请求了 GPU 却只能使用 CPU 时的原因)
+        """
+        if settings.DEVICE != "cuda":
+            return "cpu", None
+        try:
+            import onnxruntime
+        except ImportError:
+            return "cpu", "未安装 onnxruntime"
+        if "CUDAExecutionProvider" not in onnxruntime.get_available_providers():
+            return "cpu", "onnxruntime 不支持 CUDA，需要安装 onnxruntime-gpu 和 CUDA 运行库"
+        return "cuda", None
+    
+    def _local_embedding_function(self):
+        """
+        获取本地嵌入函数
+        
+        DEVICE 为 cuda 且 GPU 可用时使用 CUDA 运行 ChromaDB 默认的嵌入模型；
+        否则返回 None，由 ChromaDB 使用默认嵌入（CPU）。
+        """
+        self._device = self._resolve_device()
+        device, reason = self._device
+        if device == "cuda":
+            from chromadb.utils.embedding_functions import ONNXMiniLM_L6_V2
+            print("✓ 本地嵌入模型使用 GPU (CUDA)")
+            return ONNXMiniLM_L6_V2(
+                preferred_providers=["CUDAExecutionProvider", "CPUExecutionProvider"]
+            )
+        if reason:
+            print(f"⚠ 未能使用 GPU，本地嵌入模型运行在 CPU 上: {reason}")
+        return None
+    
+    def device_info(self) -> dict:
+        """
+        嵌入模型实际使用的计算设备
+        
+        Returns:
+            dict: device（cpu、cuda 或 remote）和 reason
+        """
+        self._get_embedding_function()
+        device, reason = self._device
+        return {"device": device, "reason": reason}
+    
     @property
     def client(self) -> chromadb.ClientAPI:
         """
@@ -114,6 +168,7 @@ class VectorService:
         当配置更新时，清除缓存的嵌入函数以便重新读取配置。
         """
         self._embedding_function = None
+        self._device = ("cpu", None)
         print("✓ 配置已重新加载")
     
     def reset_collection(self):
@@ -134,6 +189,24 @@ class VectorService:
         _ = self.collection
         print(f"✓ 集合已重置")
     
+    def embed_texts(self, texts: List[str]) -> List[List[float]]:
+        """
+        只计算文本向量，不写入向量数据库
+        
+        使用与建立索引相同的嵌入模型，用于测试嵌入速度。
+        
+        Args:
+            texts: 待向量化的文本列表
+            
+        Returns:
+            List[List[float]]: 每条文本的向量
+        """
+        embedding_fn = self._get_embedding_function()
+        if embedding_fn is None:
+            from chromadb.utils.embedding_functions import DefaultEmbeddingFunction
+            embedding_fn = DefaultEmbeddingFunction()
+        return [list(vector) for vector in embedding_fn(texts)]
+    
     def add_documents(
         self, 
         doc_id: int, 
@@ -164,13 +237,15 @@ class VectorService:
             for i, chunk in enumerate(chunks)
         ]
         
-        # 添加到集合
-        # ChromaDB 会自动使用默认的嵌入模型进行向量化
-        self.collection.add(
-            ids=ids,
-            documents=documents,
-            metadatas=metadatas
-        )
+        # 按 EMBEDDING_BATCH_SIZE 分批添加到集合，每批由嵌入模型一次向量化
+        batch_size = max(1, settings.EMBEDDING_BATCH_SIZE)
+        for start in range(0, len(chunks), batch_size):
+            end = start + batch_size
+            self.collection.add(
+                ids=ids[start:end],
+                documents=documents[start:end],
+                metadatas=metadatas[start:end]
+            )
         
         return len(chunks)
     
@@ -1,3 +1,33 @@
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
 fn main() {
+  // 构建渠道（stable/beta），决定数据目录名称
+  println!("cargo:rerun-if-env-changed=RELEASE_CHANNEL");
+  embed_backend_checksum();
   tauri_build::build()
 }
+
+// 计算打包的后端可执行文件的 SHA-256，作为 EXPECTED_BACKEND_SHA256 注入，
+// 运行时据此校验可执行文件是否被篡改或下载不完整。
+// 可通过同名环境变量直接指定；找不到可执行文件时注入空值（运行时跳过校验）
+fn embed_backend_checksum() {
+  println!("cargo:rerun-if-env-changed=EXPECTED_BACKEND_SHA256");
+  if let Ok(expected) = std::env::var("EXPECTED_BACKEND_SHA256") {
+    println!("cargo:rustc-env=EXPECTED_BACKEND_SHA256={}", expected.to_lowercase());
+    return;
+  }
+
+  let target = std::env::var("TARGET").unwrap_or_default();
+  let extension = if target.contains("windows") { ".exe" } else { "" };
+  let binary = PathBuf::from(format!("binaries/backend-{}{}", target, extension));
+  println!("cargo:rerun-if-changed={}", binary.display());
+  let checksum = match std::fs::read(&binary) {
+    Ok(bytes) if !bytes.is_empty() => format!("{:x}", Sha256::digest(&bytes)),
+    _ => {
+      println!("cargo:warning=未找到后端可执行文件 {}，不注入校验值", binary.display());
+      String::new()
+    }
+  };
+  println!("cargo:rustc-env=EXPECTED_BACKEND_SHA256={}", checksum);
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::State;
+
+use crate::http::{self, BackendClient};
+
+// 没有 /api-version 接口的旧后端按该版本处理
+const LEGACY_API_VERSION: &str = "1.0.0";
+// 旧后端也提供的功能（流式问答是最早就有的接口）
+const LEGACY_FEATURES: [&str; 1] = ["streaming"];
+
+// 后端的接口版本和支持的可选功能，前端据此隐藏旧后端不支持的操作
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendApiVersion {
+    // 语义化版本号
+    pub version: String,
+    // 支持的可选功能，例如 streaming、task_cancellation
+    pub features: Vec<String>,
+    // 后端是否提供 /api-version 接口；为 false 时以上是旧后端的默认值
+    #[serde(default = "reported")]
+    pub supported: bool,
+}
+
+fn reported() -> bool {
+    true
+}
+
+impl BackendApiVersion {
+    fn legacy() -> Self {
+        Self {
+            version: LEGACY_API_VERSION.to_string(),
+            features: LEGACY_FEATURES.iter().map(|f| f.to_string()).collect(),
+            supported: false,
+        }
+    }
+}
+
+// 第一次查询成功后缓存结果，后端重新启动时清除（可能换成了其他版本的后端）
+pub struct ApiVersionCache(Mutex<Option<BackendApiVersion>>);
+
+impl ApiVersionCache {
+    pub fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+
+    pub fn clear(&self) {
+        self.0.lock().unwrap().take();
+    }
+}
+
+async fn fetch(client: &BackendClient) -> Result<BackendApiVersion, String> {
+    let response = http::send(client.get("/api-version"), true)
+        .await
+        .map_err(|e| format!("连接后端失败: {}", e))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(BackendApiVersion::legacy());
+    }
+    if !status.is_success() {
+        return Err(format!("后端返回错误状态 {}", status));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("解析后端响应失败: {}", e))
+}
+
+// 查询后端的接口版本和支持的功能；后端没有该接口时返回旧后端的默认值
+#[tauri::command]
+pub async fn get_backend_api_version(
+    client: State<'_, BackendClient>,
+    cache: State<'_, ApiVersionCache>,
+) -> Result<BackendApiVersion, String> {
+    if let Some(version) = cache.0.lock().unwrap().clone() {
+        return Ok(version);
+    }
+    let version = fetch(&client).await?;
+    log::info!(
+        "后端接口版本 {}，支持的功能: {:?}",
+        version.version,
+        version.features
+    );
+    *cache.0.lock().unwrap() = Some(version.clone());
+    Ok(version)
+}
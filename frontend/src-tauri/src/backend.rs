@@ -0,0 +1,672 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_shell::process::{Command, CommandChild, CommandEvent, TerminatedPayload};
+use tauri_plugin_shell::ShellExt;
+use tokio_util::sync::CancellationToken;
+
+use crate::apiversion::ApiVersionCache;
+use crate::cache;
+use crate::cgroup;
+use crate::config::{is_wildcard_host, ConfigState};
+use crate::detached;
+use crate::device;
+use crate::http::{check_health, BackendClient, BACKEND_PORT};
+use crate::logs;
+use crate::paths::AppPaths;
+use crate::safemode::SafeMode;
+use crate::sidecar::{self, BackendSource};
+use crate::update::SidecarVersion;
+use crate::vault;
+use crate::warmup::Warmup;
+use crate::watchdog::{MemoryHistory, MemorySample};
+
+// 失败报告中保留的 stdout/stderr 末尾行数
+const OUTPUT_TAIL_LINES: usize = 20;
+// 等待后端就绪的最长时间（首次加载模型可能较慢）
+const READY_TIMEOUT: Duration = Duration::from_secs(120);
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+// 进程退出后继续读取剩余输出的最长时间
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+// 输出管道关闭后检查后端进程是否仍在运行的间隔
+pub const LIVENESS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+// 重启或执行文档库维护前等待旧进程退出的最长时间
+pub const STOP_WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// 存储后端进程的全局状态
+pub struct BackendProcess {
+    pub child: Mutex<Option<CommandChild>>,
+    // 独立运行（detached_backend）的后端进程号，这种后端没有子进程句柄
+    pub detached: Mutex<Option<u32>>,
+    // 最近一次后端失败的详情，成功启动后清除
+    pub last_error: Mutex<Option<BackendFailure>>,
+    // 应用退出时取消：中止正在进行的启动和等待，后台监控任务也随之结束
+    pub shutdown: CancellationToken,
+}
+
+impl BackendProcess {
+    pub fn new() -> Self {
+        Self {
+            child: Mutex::new(None),
+            detached: Mutex::new(None),
+            last_error: Mutex::new(None),
+            shutdown: CancellationToken::new(),
+        }
+    }
+
+    // 应用是否正在退出
+    pub fn shutting_down(&self) -> bool {
+        self.shutdown.is_cancelled()
+    }
+
+    // 当前运行中的后端进程 pid
+    pub fn pid(&self) -> Option<u32> {
+        self.child
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|child| child.pid())
+            .or_else(|| self.detached_pid())
+    }
+
+    // 当前独立运行的后端进程号
+    pub fn detached_pid(&self) -> Option<u32> {
+        *self.detached.lock().unwrap()
+    }
+
+    // 当前记录的子进程是否就是指定 pid 的进程
+    fn is_current(&self, pid: u32) -> bool {
+        self.pid() == Some(pid)
+    }
+}
+
+// 后端失败的大致原因，便于前端给出针对性的提示
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    SpawnFailed,
+    IntegrityFailed,
+    PortInUse,
+    MissingDependency,
+    PermissionDenied,
+    // 被系统因内存不足终止（如 Linux 的 OOM killer）
+    OutOfMemory,
+    Killed,
+    Crashed,
+}
+
+// 一次后端失败的详情
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendFailure {
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
+    pub stdout_tail: Vec<String>,
+    pub stderr_tail: Vec<String>,
+    // Unix 时间戳（毫秒）
+    pub timestamp: u64,
+    pub kind: FailureKind,
+    pub message: String,
+    // 内存不足导致的失败附带最近的内存采样
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub memory_samples: Vec<MemorySample>,
+}
+
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// 根据 stderr 输出和退出状态判断失败原因
+fn classify(payload: &TerminatedPayload, stderr_tail: &[String]) -> FailureKind {
+    let text = stderr_tail.join("\n").to_lowercase();
+    if text.contains("address already in use") || text.contains("10048") {
+        FailureKind::PortInUse
+    } else if text.contains("modulenotfounderror") || text.contains("importerror") {
+        FailureKind::MissingDependency
+    } else if text.contains("permission denied") || text.contains("permissionerror") {
+        FailureKind::PermissionDenied
+    } else if looks_like_oom(payload, &text) {
+        FailureKind::OutOfMemory
+    } else if payload.signal.is_some() {
+        FailureKind::Killed
+    } else {
+        FailureKind::Crashed
+    }
+}
+
+// Windows 上内存不足时的退出码：STATUS_NO_MEMORY、STATUS_COMMITMENT_LIMIT
+const WINDOWS_OOM_EXIT_CODES: [i32; 2] = [0xC000_0017_u32 as i32, 0xC000_012D_u32 as i32];
+// 内存不足时给用户的建议
+const OOM_HINT: &str =
+    "后端因内存不足被系统终止。请尝试减小 embedding_batch_size、降低导入并发数，或改用较小的嵌入模型";
+
+// 是否像是因内存不足被终止：Python 报告 MemoryError，Linux 上被 SIGKILL（OOM killer 的信号，
+// 通过 systemd-run 启动时表现为退出码 137），或 Windows 上的内存不足退出码
+fn looks_like_oom(payload: &TerminatedPayload, stderr: &str) -> bool {
+    if stderr.contains("memoryerror") || stderr.contains("out of memory") {
+        return true;
+    }
+    if cfg!(target_os = "linux") {
+        payload.signal == Some(9) || payload.code == Some(137)
+    } else if cfg!(windows) {
+        payload
+            .code
+            .is_some_and(|code| WINDOWS_OOM_EXIT_CODES.contains(&code))
+    } else {
+        false
+    }
+}
+
+// 记录失败并通知前端；内存不足时附上最近的内存采样并额外发送 backend-oom-killed 事件
+pub fn report_failure(app: &AppHandle, mut failure: BackendFailure) {
+    if matches!(failure.kind, FailureKind::OutOfMemory) {
+        failure.memory_samples = app.state::<MemoryHistory>().snapshot();
+        failure.message = format!("{}（{}）", OOM_HINT, failure.message);
+        let _ = app.emit(
+            "backend-oom-killed",
+            serde_json::json!({
+                "message": OOM_HINT,
+                "exit_code": failure.exit_code,
+                "signal": failure.signal,
+                "memory_samples": failure.memory_samples,
+            }),
+        );
+    }
+    log::error!(
+        "[Backend] 后端失败 ({:?}): {}",
+        failure.kind,
+        failure.message
+    );
+    logs::write_crash_report(app, &failure);
+    let _ = app.emit("backend-failed", &failure);
+    *app.state::<BackendProcess>().last_error.lock().unwrap() = Some(failure);
+}
+
+// 启动打包的后端服务，并异步转发其输出到日志
+pub async fn spawn_backend(app: &AppHandle) -> Result<(), String> {
+    if app.state::<BackendProcess>().shutting_down() {
+        return Err("应用正在退出，不再启动后端".to_string());
+    }
+    // 可执行文件校验未通过时拒绝启动
+    let (result, kind) = match sidecar::ensure_integrity(app).await {
+        Ok(_) => (try_spawn_backend(app), FailureKind::SpawnFailed),
+        Err(e) => (Err(e), FailureKind::IntegrityFailed),
+    };
+    if let Err(message) = &result {
+        report_failure(
+            app,
+            BackendFailure {
+                exit_code: None,
+                signal: None,
+                stdout_tail: Vec::new(),
+                stderr_tail: Vec::new(),
+                timestamp: now_millis(),
+                kind,
+                message: message.clone(),
+                memory_samples: Vec::new(),
+            },
+        );
+    }
+    result
+}
+
+// 由应用管理的启动参数，额外参数中不允许再出现
+const MANAGED_FLAGS: [&str; 2] = ["--port", "--data-dir"];
+
+// 后端的启动方案：可执行文件、参数、环境变量和工作目录
+#[derive(Debug, Clone, Serialize)]
+pub struct SpawnPlan {
+    pub program: String,
+    // 打包的后端或 custom_backend_path 指定的后端
+    pub source: BackendSource,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    pub work_dir: PathBuf,
+    // 仅 Linux：通过 systemd-run 施加的资源限制属性
+    pub resource_limits: Vec<String>,
+    // 是否作为独立进程启动（应用退出后继续运行）
+    pub detached: bool,
+}
+
+// 检查额外参数是否与应用管理的参数冲突
+pub fn validate_extra_args(extra_args: &[String]) -> Result<(), String> {
+    for arg in extra_args {
+        let flag = arg.split('=').next().unwrap_or(arg);
+        if MANAGED_FLAGS.contains(&flag) {
+            return Err(format!("额外参数 {} 与应用管理的参数冲突", arg));
+        }
+    }
+    Ok(())
+}
+
+// 根据当前配置生成启动方案
+pub fn spawn_plan(app: &AppHandle) -> Result<SpawnPlan, String> {
+    let paths = app.state::<AppPaths>();
+    let data_dir = paths.data_dir.clone();
+    // 全局配置叠加当前文档库 vault.toml 中的覆盖项
+    let config = vault::effective_config(app)?;
+    validate_extra_args(&config.backend_extra_args)?;
+
+    // 工作目录可以与数据目录分开（例如放在更快的本地磁盘上），
+    // 数据位置通过 DATA_DIR 等环境变量传递，不依赖工作目录
+    let work_dir = config
+        .backend_work_dir
+        .clone()
+        .unwrap_or_else(|| data_dir.clone());
+
+    if is_wildcard_host(&config.backend_host) {
+        log::warn!(
+            "后端监听 {}，已暴露给局域网中的其他设备",
+            config.backend_host
+        );
+    }
+
+    // 后端不解析命令行参数，所有配置都通过下面的环境变量传递（后端的 pydantic 配置读取这些变量）。
+    // --port 和 --data-dir 只用于在进程列表中识别本应用启动的后端（见 processes::is_backend）
+    let mut args = vec![
+        "--port".to_string(),
+        BACKEND_PORT.to_string(),
+        "--data-dir".to_string(),
+        data_dir.to_string_lossy().to_string(),
+    ];
+    args.extend(config.backend_extra_args.iter().cloned());
+    let safe_mode = app.state::<SafeMode>().is_active();
+
+    let mut env = vec![
+        ("HOST".to_string(), config.backend_host.clone()),
+        ("PORT".to_string(), BACKEND_PORT.to_string()),
+        (
+            "EMBEDDING_BATCH_SIZE".to_string(),
+            config.embedding_batch_size.to_string(),
+        ),
+        (
+            "INGEST_CONCURRENCY".to_string(),
+            config.ingest_concurrency.to_string(),
+        ),
+        (
+            "DEVICE".to_string(),
+            device::device_arg(config.gpu_enabled).to_string(),
+        ),
+        (
+            "EMBEDDING_PROVIDER".to_string(),
+            config.embedding_provider.as_str().to_string(),
+        ),
+        // 后端切分文档时使用的切片大小和重叠
+        ("CHUNK_SIZE".to_string(), config.chunk_size.to_string()),
+        (
+            "CHUNK_OVERLAP".to_string(),
+            config.chunk_overlap.to_string(),
+        ),
+    ];
+    if safe_mode {
+        env.push(("SAFE_MODE".to_string(), "1".to_string()));
+    }
+    // 文档库大小上限由后端的上传接口统一检查，界面直接上传的文件同样受限
+    if let Some(max) = config.max_vault_bytes {
+        env.push(("MAX_VAULT_BYTES".to_string(), max.to_string()));
+    }
+    // 模型下载等缓存放在缓存目录（Python 库通过 XDG_CACHE_HOME 确定缓存位置）
+    let cache_dir = cache::cache_dir(&paths, &config);
+    if cache::check_cache_dir(&paths, &cache_dir).is_ok() {
+        let cache_dir = cache_dir.to_string_lossy().to_string();
+        env.push(("CACHE_DIR".to_string(), cache_dir.clone()));
+        env.push(("XDG_CACHE_HOME".to_string(), cache_dir));
+    }
+    // 总是把数据库、索引和上传目录指向当前文档库：打包的后端默认使用固定的位置，
+    // 不区分构建渠道，也不知道用户选择的数据目录
+    env.extend(vault::storage_env(&paths.vault_dir()));
+
+    let source = sidecar::source(&app.state::<ConfigState>().get());
+    if let BackendSource::Custom { path } = &source {
+        log::info!("使用自定义后端: {:?}", path);
+    }
+    Ok(SpawnPlan {
+        program: match &source {
+            BackendSource::Bundled => "backend".to_string(),
+            BackendSource::Custom { path } => path.to_string_lossy().to_string(),
+        },
+        source,
+        args,
+        env,
+        work_dir,
+        resource_limits: cgroup::resource_limits(&config),
+        detached: config.detached_backend,
+    })
+}
+
+// 启动方案对应的后端命令（不含参数）：打包的 sidecar 或自定义的可执行文件
+pub fn base_command(app: &AppHandle, plan: &SpawnPlan) -> Result<Command, String> {
+    match &plan.source {
+        BackendSource::Bundled => app
+            .shell()
+            .sidecar(&plan.program)
+            .map_err(|e| format!("无法找到后端可执行文件: {}", e)),
+        BackendSource::Custom { path } => Ok(app.shell().command(path)),
+    }
+}
+
+fn try_spawn_backend(app: &AppHandle) -> Result<(), String> {
+    let plan = spawn_plan(app)?;
+    if let Err(e) = std::fs::create_dir_all(&plan.work_dir) {
+        log::warn!("创建后端工作目录失败 {:?}: {}", plan.work_dir, e);
+    }
+    if plan.detached {
+        let pid = detached::spawn(app, &plan)?;
+        log::info!("独立后端已启动 (pid {})", pid);
+        app.state::<SidecarVersion>().record_spawn(&plan.source);
+        adopt_detached(app, pid);
+        return Ok(());
+    }
+
+    // 配置了资源限制时（仅 Linux）通过 systemd-run 在临时 scope 中启动
+    #[cfg(target_os = "linux")]
+    let scoped = plan.source.program_path().and_then(|program| {
+        cgroup::scope_args(
+            &plan.resource_limits,
+            &program.to_string_lossy(),
+            &plan.args,
+        )
+    });
+    #[cfg(not(target_os = "linux"))]
+    let scoped: Option<Vec<String>> = None;
+    let command = match scoped {
+        Some(args) => {
+            log::info!(
+                "在 systemd scope 中启动后端: {}",
+                plan.resource_limits.join(", ")
+            );
+            app.shell().command("systemd-run").args(args)
+        }
+        None => base_command(app, &plan)?.args(&plan.args),
+    };
+    let sidecar = command.current_dir(&plan.work_dir).envs(plan.env);
+
+    let (mut rx, child) = sidecar
+        .spawn()
+        .map_err(|e| format!("启动后端服务失败: {}", e))?;
+    let pid = child.pid();
+    log::info!("后端服务已启动 (pid {})", pid);
+
+    // 存储子进程句柄，并清除上一次的失败记录
+    let state = app.state::<BackendProcess>();
+    *state.child.lock().unwrap() = Some(child);
+    *state.last_error.lock().unwrap() = None;
+    // 启动期间用户已经退出应用：立即终止刚启动的进程，避免留下孤儿进程
+    if state.shutting_down() {
+        stop_backend(app);
+        return Ok(());
+    }
+    app.state::<Warmup>().reset();
+    app.state::<ApiVersionCache>().clear();
+    app.state::<SidecarVersion>().record_spawn(&plan.source);
+    wait_until_ready(app.clone(), pid);
+
+    // 异步读取后端输出
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut tail = OutputTail::default();
+        let mut terminated = false;
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Terminated(payload) => {
+                    terminated = true;
+                    // 进程退出后管道里可能还有未读取的输出，读完再生成失败报告
+                    let drain = async {
+                        while let Some(event) = rx.recv().await {
+                            record_output(&app, &mut tail, event);
+                        }
+                    };
+                    if tokio::time::timeout(OUTPUT_DRAIN_TIMEOUT, drain)
+                        .await
+                        .is_err()
+                    {
+                        log::warn!("读取后端剩余输出超时");
+                    }
+                    log::info!("[Backend] 进程退出: {:?}", payload);
+                    // 子进程句柄已被 stop_backend 取走说明是主动终止，不算失败
+                    let state = app.state::<BackendProcess>();
+                    if state.is_current(pid) {
+                        state.child.lock().unwrap().take();
+                        let stdout_tail: Vec<String> = tail.stdout.drain(..).collect();
+                        let stderr_tail: Vec<String> = tail.stderr.drain(..).collect();
+                        report_failure(
+                            &app,
+                            BackendFailure {
+                                exit_code: payload.code,
+                                signal: payload.signal,
+                                kind: classify(&payload, &stderr_tail),
+                                stdout_tail,
+                                stderr_tail,
+                                timestamp: now_millis(),
+                                message: format!("后端进程意外退出: {:?}", payload),
+                                memory_samples: Vec::new(),
+                            },
+                        );
+                    }
+                    break;
+                }
+                event => record_output(&app, &mut tail, event),
+            }
+        }
+        // 输出通道结束但没有收到退出事件：后端可能关闭了输出管道（例如转入后台运行）
+        if !terminated {
+            watch_without_output(app, pid, tail).await;
+        }
+    });
+
+    Ok(())
+}
+
+// 接管一个独立运行的后端：记录进程号，等待就绪并监控其是否退出
+pub fn adopt_detached(app: &AppHandle, pid: u32) {
+    let state = app.state::<BackendProcess>();
+    *state.detached.lock().unwrap() = Some(pid);
+    *state.last_error.lock().unwrap() = None;
+    app.state::<Warmup>().reset();
+    app.state::<ApiVersionCache>().clear();
+    wait_until_ready(app.clone(), pid);
+    tauri::async_runtime::spawn(detached::monitor(app.clone(), pid));
+}
+
+pub fn process_alive(system: &mut sysinfo::System, pid: u32) -> bool {
+    let pid = sysinfo::Pid::from_u32(pid);
+    system.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[pid]), true);
+    system.process(pid).is_some()
+}
+
+// 后端关闭输出管道后不再读取输出，但仍保留进程并定期确认它是否在运行；
+// 进程随后退出时按意外退出处理（此时无法获得退出码）
+async fn watch_without_output(app: AppHandle, pid: u32, mut tail: OutputTail) {
+    let mut system = sysinfo::System::new();
+    if process_alive(&mut system, pid) {
+        log::info!(
+            "[Backend] 后端关闭了输出管道，停止读取输出，进程继续运行 (pid {})",
+            pid
+        );
+    }
+    loop {
+        let state = app.state::<BackendProcess>();
+        if !state.is_current(pid) {
+            return;
+        }
+        if !process_alive(&mut system, pid) {
+            break;
+        }
+        tokio::time::sleep(LIVENESS_POLL_INTERVAL).await;
+    }
+
+    let state = app.state::<BackendProcess>();
+    if !state.is_current(pid) {
+        return;
+    }
+    state.child.lock().unwrap().take();
+    let stderr_tail: Vec<String> = tail.stderr.drain(..).collect();
+    report_failure(
+        &app,
+        BackendFailure {
+            exit_code: None,
+            signal: None,
+            stdout_tail: tail.stdout.drain(..).collect(),
+            stderr_tail,
+            timestamp: now_millis(),
+            kind: FailureKind::Crashed,
+            message: format!(
+                "后端进程 (pid {}) 已退出，输出管道已关闭，无法获取退出状态",
+                pid
+            ),
+            memory_samples: Vec::new(),
+        },
+    );
+}
+
+// 后端最近的输出，用于失败报告
+#[derive(Default)]
+struct OutputTail {
+    stdout: VecDeque<String>,
+    stderr: VecDeque<String>,
+}
+
+fn push_tail(tail: &mut VecDeque<String>, line: &str) {
+    if tail.len() == OUTPUT_TAIL_LINES {
+        tail.pop_front();
+    }
+    tail.push_back(line.to_string());
+}
+
+// 记录后端的一条输出，并保留末尾几行用于失败报告
+fn record_output(app: &AppHandle, tail: &mut OutputTail, event: CommandEvent) {
+    match event {
+        CommandEvent::Stdout(line) => {
+            let line = String::from_utf8_lossy(&line).trim_end().to_string();
+            push_tail(&mut tail.stdout, &line);
+            logs::record(app, "stdout", "info", line);
+        }
+        CommandEvent::Stderr(line) => {
+            let line = String::from_utf8_lossy(&line).trim_end().to_string();
+            push_tail(&mut tail.stderr, &line);
+            logs::record(app, "stderr", logs::stderr_level(&line), line);
+        }
+        CommandEvent::Error(err) => {
+            logs::record(app, "stderr", "error", format!("Error: {}", err));
+        }
+        _ => {}
+    }
+}
+
+// 轮询健康检查，后端可以响应请求后发送 backend-ready 事件
+fn wait_until_ready(app: AppHandle, pid: u32) {
+    tauri::async_runtime::spawn(async move {
+        let shutdown = app.state::<BackendProcess>().shutdown.clone();
+        let wait = async {
+            let client = app.state::<BackendClient>();
+            let started = Instant::now();
+            while started.elapsed() < READY_TIMEOUT {
+                // 等待期间后端已退出或被替换，交给对应的流程处理
+                if !app.state::<BackendProcess>().is_current(pid) {
+                    return;
+                }
+                if check_health(&client).await {
+                    log::info!("后端服务已就绪，用时 {} ms", started.elapsed().as_millis());
+                    let _ = app.emit("backend-ready", ());
+                    device::check_after_ready(&app);
+                    return;
+                }
+                tokio::time::sleep(READY_POLL_INTERVAL).await;
+            }
+            log::warn!("后端在 {} 秒内未就绪", READY_TIMEOUT.as_secs());
+        };
+        if shutdown.run_until_cancelled(wait).await.is_none() {
+            log::info!("应用退出，停止等待后端就绪");
+        }
+    });
+}
+
+// 应用退出：取消正在进行的启动和等待，并终止后端进程。
+// 独立运行的后端保持运行，下次启动时重新连接；需要结束时使用 stop_detached_backend
+pub fn shutdown_backend(app: &AppHandle) {
+    let state = app.state::<BackendProcess>();
+    state.shutdown.cancel();
+    if let Some(pid) = state.detached.lock().unwrap().take() {
+        log::info!("独立后端继续运行 (pid {})", pid);
+        return;
+    }
+    stop_backend(app);
+}
+
+// 终止后端进程（如果正在运行）
+pub fn stop_backend(app: &AppHandle) {
+    let state = app.state::<BackendProcess>();
+    // 先获取 child，释放锁后再使用
+    let child_option = { state.child.lock().unwrap().take() };
+    if let Some(child) = child_option {
+        match child.kill() {
+            Ok(_) => log::info!("后端服务已终止"),
+            Err(e) => log::error!("终止后端服务失败: {}", e),
+        }
+    }
+    // 重启、文档库维护等需要停止后端时，独立运行的后端同样终止
+    let detached_option = { state.detached.lock().unwrap().take() };
+    if let Some(pid) = detached_option {
+        detached::kill(app, pid);
+    }
+}
+
+// 终止后端并等待进程真正退出、端口释放，超时返回 false
+pub async fn stop_backend_and_wait(app: &AppHandle, timeout: Duration) -> bool {
+    let Some(pid) = app.state::<BackendProcess>().pid() else {
+        return true;
+    };
+    stop_backend(app);
+
+    let pid = sysinfo::Pid::from_u32(pid);
+    let mut system = sysinfo::System::new();
+    let started = Instant::now();
+    while started.elapsed() < timeout {
+        system.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[pid]), true);
+        let port_open = std::net::TcpStream::connect_timeout(
+            &([127, 0, 0, 1], BACKEND_PORT).into(),
+            READY_POLL_INTERVAL,
+        )
+        .is_ok();
+        if system.process(pid).is_none() && !port_open {
+            return true;
+        }
+        tokio::time::sleep(READY_POLL_INTERVAL).await;
+    }
+    log::warn!("等待后端进程 (pid {}) 退出超时", pid);
+    false
+}
+
+// 重启后端以应用新的配置：等旧进程退出、端口释放后再启动新进程，避免两者争抢端口
+pub async fn restart_backend(app: &AppHandle) -> Result<(), String> {
+    if app.state::<BackendProcess>().shutting_down() {
+        return Ok(());
+    }
+    if cfg!(debug_assertions) {
+        // 开发模式下后端由开发者自行启动，需要手动重启
+        log::info!("开发模式：请手动重启后端以应用新配置");
+        return Ok(());
+    }
+    log::info!("正在重启后端服务...");
+    if !stop_backend_and_wait(app, STOP_WAIT_TIMEOUT).await {
+        return Err("旧的后端进程未能及时退出，请稍后重试".to_string());
+    }
+    spawn_backend(app).await
+}
+
+// 获取最近一次后端失败的详情（没有失败记录时返回 None）
+#[tauri::command]
+pub fn get_last_error(backend: State<'_, BackendProcess>) -> Option<BackendFailure> {
+    backend.last_error.lock().unwrap().clone()
+}
+
+// 只生成启动方案而不真正启动，便于排查启动参数问题
+#[tauri::command]
+pub fn dry_run_spawn(app: AppHandle) -> Result<SpawnPlan, String> {
+    spawn_plan(&app)
+}
@@ -0,0 +1,281 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::backend::{now_millis, BackendProcess};
+use crate::config::{AppConfig, BackupSchedule, ConfigState};
+use crate::http::BackendClient;
+use crate::import::IngestQueue;
+use crate::index::IndexMaintenance;
+use crate::paths::AppPaths;
+use crate::vault::{self, BackupResult};
+use crate::watchdog;
+
+// 数据目录中记录上次自动备份时间的文件，应用重启后按该时间继续计划
+const STATE_FILE: &str = "backup-state.json";
+// 默认备份目录（位于数据目录下）
+const BACKUP_DIR_NAME: &str = "backups";
+// 检查是否到了备份时间的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+// 正在建立索引时推迟备份，备份失败后也等待该时间再重试
+const RETRY_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BackupRecord {
+    // 上次自动备份完成的时间（Unix 时间戳，毫秒）
+    last_backup_at: Option<u64>,
+}
+
+// 自动备份的状态：上次备份时间保存在磁盘上
+pub struct BackupState {
+    path: PathBuf,
+    record: Mutex<BackupRecord>,
+}
+
+impl BackupState {
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(STATE_FILE);
+        let record = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            record: Mutex::new(record),
+        }
+    }
+
+    fn last_backup_at(&self) -> Option<u64> {
+        self.record.lock().unwrap().last_backup_at
+    }
+
+    // 先写临时文件再重命名，避免写入中途崩溃留下半个文件
+    fn set_last_backup_at(&self, timestamp: u64) -> Result<(), String> {
+        let mut record = self.record.lock().unwrap();
+        record.last_backup_at = Some(timestamp);
+        let text = serde_json::to_string_pretty(&*record).map_err(|e| e.to_string())?;
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, text).map_err(|e| format!("写入备份记录失败: {}", e))?;
+        std::fs::rename(&tmp, &self.path).map_err(|e| format!("保存备份记录失败: {}", e))
+    }
+}
+
+// 自动备份保存的目录：未配置时使用数据目录下的 backups
+pub fn backup_dir(paths: &AppPaths, config: &AppConfig) -> PathBuf {
+    config
+        .backup_dir
+        .clone()
+        .unwrap_or_else(|| paths.data_dir.join(BACKUP_DIR_NAME))
+}
+
+// 备份目录不能位于文档库内，否则每次备份都会把之前的备份打包进去。
+// 已存在的路径先解析符号链接和 `..` 再比较
+pub fn check_backup_dir(paths: &AppPaths, dir: &Path) -> Result<(), String> {
+    if !dir.is_absolute() {
+        return Err(format!("备份目录必须是绝对路径: {}", dir.display()));
+    }
+    let resolve = |path: &Path| std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let resolved = resolve(dir);
+    for vault_dir in [paths.default_vault_dir(), paths.vault_dir()] {
+        if resolved.starts_with(resolve(&vault_dir)) || dir.starts_with(&vault_dir) {
+            return Err(format!("备份目录不能位于文档库内: {}", dir.display()));
+        }
+    }
+    Ok(())
+}
+
+// 下次自动备份的时间（Unix 时间戳，毫秒）；从未备份过时为当前时间，关闭时为 None
+fn next_backup_at(schedule: BackupSchedule, last_backup_at: Option<u64>) -> Option<u64> {
+    let interval = schedule.interval()?.as_millis() as u64;
+    Some(last_backup_at.map_or_else(now_millis, |last| last + interval))
+}
+
+// 删除超出保留份数的旧备份（按文件名中的时间戳排序），返回删除的数量
+fn prune(dir: &Path, retention: u32) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut backups: Vec<(u64, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let timestamp = vault::backup_timestamp(&entry.file_name().to_string_lossy())?;
+            Some((timestamp, entry.path()))
+        })
+        .collect();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.0));
+    let mut pruned = 0;
+    for (_, path) in backups.into_iter().skip(retention as usize) {
+        match std::fs::remove_file(&path) {
+            Ok(_) => pruned += 1,
+            Err(e) => log::warn!("删除旧备份 {:?} 失败: {}", path, e),
+        }
+    }
+    pruned
+}
+
+// 自动备份的结果，作为 backup-completed 事件发送
+#[derive(Debug, Serialize)]
+struct ScheduledBackup {
+    #[serde(flatten)]
+    result: BackupResult,
+    // 按保留份数删除的旧备份数量
+    pruned: usize,
+}
+
+// 执行一次自动备份。正在建立索引、导入或执行其他维护任务时跳过并返回 None
+async fn run_backup(
+    app: &AppHandle,
+    config: &AppConfig,
+) -> Result<Option<ScheduledBackup>, String> {
+    if app.state::<IngestQueue>().is_importing()
+        || watchdog::is_indexing(&app.state::<BackendClient>()).await
+    {
+        return Ok(None);
+    }
+    let maintenance = app.state::<IndexMaintenance>();
+    let Ok(_guard) = maintenance.begin("自动备份") else {
+        return Ok(None);
+    };
+
+    let dir = backup_dir(&app.state::<AppPaths>(), config);
+    let result = vault::create_backup(app, &dir).await?;
+    app.state::<BackupState>()
+        .set_last_backup_at(result.manifest.created_at)?;
+    let pruned = prune(&dir, config.backup_retention);
+    if pruned > 0 {
+        log::info!("已删除 {} 个超出保留份数的旧备份", pruned);
+    }
+    Ok(Some(ScheduledBackup { result, pruned }))
+}
+
+// 按 backup_schedule 定期备份文档库，完成后发送 backup-completed，失败时发送 backup-failed。
+// 正在建立索引时推迟，稍后重试
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut retry_at: Option<Instant> = None;
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            if app.state::<BackendProcess>().shutting_down() {
+                break;
+            }
+            if retry_at.is_some_and(|at| Instant::now() < at) {
+                continue;
+            }
+            let config = app.state::<ConfigState>().get();
+            let last = app.state::<BackupState>().last_backup_at();
+            match next_backup_at(config.backup_schedule, last) {
+                Some(next) if next <= now_millis() => {}
+                _ => continue,
+            }
+
+            match run_backup(&app, &config).await {
+                Ok(Some(backup)) => {
+                    retry_at = None;
+                    log::info!("自动备份完成: {}", backup.result.archive_path);
+                    let _ = app.emit("backup-completed", &backup);
+                }
+                Ok(None) => {
+                    retry_at = Some(Instant::now() + RETRY_INTERVAL);
+                    log::info!(
+                        "正在建立索引或执行维护任务，自动备份推迟 {} 分钟",
+                        RETRY_INTERVAL.as_secs() / 60
+                    );
+                }
+                Err(e) => {
+                    retry_at = Some(Instant::now() + RETRY_INTERVAL);
+                    log::error!("自动备份失败: {}", e);
+                    let _ = app.emit("backup-failed", json!({ "error": e }));
+                }
+            }
+        }
+    });
+}
+
+// 自动备份的配置和状态
+#[derive(Debug, Serialize)]
+pub struct BackupScheduleStatus {
+    pub schedule: BackupSchedule,
+    pub retention: u32,
+    pub backup_dir: PathBuf,
+    pub last_backup_at: Option<u64>,
+    pub next_backup_at: Option<u64>,
+}
+
+fn schedule_status(app: &AppHandle, config: &AppConfig) -> BackupScheduleStatus {
+    let last_backup_at = app.state::<BackupState>().last_backup_at();
+    BackupScheduleStatus {
+        schedule: config.backup_schedule,
+        retention: config.backup_retention,
+        backup_dir: backup_dir(&app.state::<AppPaths>(), config),
+        last_backup_at,
+        next_backup_at: next_backup_at(config.backup_schedule, last_backup_at),
+    }
+}
+
+#[tauri::command]
+pub fn get_backup_schedule(app: AppHandle, config: State<'_, ConfigState>) -> BackupScheduleStatus {
+    schedule_status(&app, &config.get())
+}
+
+// 设置自动备份的频率、保留份数和目录（dir 为 None 时使用默认目录）
+#[tauri::command]
+pub fn set_backup_schedule(
+    app: AppHandle,
+    config: State<'_, ConfigState>,
+    schedule: BackupSchedule,
+    retention: u32,
+    dir: Option<String>,
+) -> Result<BackupScheduleStatus, String> {
+    let dir = dir
+        .filter(|dir| !dir.trim().is_empty())
+        .map(|dir| PathBuf::from(dir.trim()));
+    if let Some(dir) = &dir {
+        check_backup_dir(&app.state::<AppPaths>(), dir)?;
+    }
+    let mut next = config.get();
+    next.backup_schedule = schedule;
+    next.backup_retention = retention;
+    next.backup_dir = dir;
+    next.validate()?;
+    config.set(next.clone())?;
+    log::info!("自动备份已更新: {:?}，保留 {} 份", schedule, retention);
+    Ok(schedule_status(&app, &next))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_paths(name: &str) -> AppPaths {
+        let data_dir =
+            std::env::temp_dir().join(format!("document-qa-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&data_dir);
+        std::fs::create_dir_all(data_dir.join("data").join("uploads")).unwrap();
+        std::fs::create_dir_all(data_dir.join("logs")).unwrap();
+        AppPaths::new(data_dir.clone(), data_dir.join("logs"), None)
+    }
+
+    #[test]
+    fn backup_dir_inside_vault_is_rejected() {
+        let paths = test_paths("backup-inside");
+        let vault = paths.default_vault_dir();
+        assert!(check_backup_dir(&paths, &vault).is_err());
+        assert!(check_backup_dir(&paths, &vault.join("backups")).is_err());
+        // 经过 `..` 绕回文档库的路径同样拒绝
+        let detour = paths.log_dir.join("..").join("data").join("uploads");
+        assert!(check_backup_dir(&paths, &detour).is_err());
+        std::fs::remove_dir_all(&paths.data_dir).unwrap();
+    }
+
+    #[test]
+    fn backup_dir_outside_vault_is_accepted() {
+        let paths = test_paths("backup-outside");
+        check_backup_dir(&paths, &paths.data_dir.join(BACKUP_DIR_NAME)).unwrap();
+        assert!(check_backup_dir(&paths, Path::new("backups")).is_err());
+        std::fs::remove_dir_all(&paths.data_dir).unwrap();
+    }
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::{Duration, Instant};
+use tauri::State;
+
+use crate::config::ConfigState;
+use crate::http::{self, BackendClient};
+
+// 单次测试最多向量化的文本数（与后端接口的上限一致）
+const MAX_SAMPLE_SIZE: usize = 1024;
+// 依次测试的批大小
+const BATCH_SIZES: [usize; 5] = [8, 16, 32, 64, 128];
+// 单个批次的超时时间（CPU 上的本地模型可能较慢）
+const BATCH_TIMEOUT: Duration = Duration::from_secs(120);
+// 生成测试文本用的词表
+const WORDS: &str =
+    "文档 检索 向量 模型 索引 问答 系统 数据 查询 段落 语义 测试 document search embedding context";
+
+// 后端返回的一批文本的向量化结果
+#[derive(Debug, Deserialize)]
+struct BatchResult {
+    count: usize,
+    dimensions: usize,
+}
+
+// 某个批大小下的测试结果
+#[derive(Debug, Serialize)]
+pub struct BenchmarkRun {
+    pub batch_size: usize,
+    pub embeddings_per_sec: f64,
+    // 每批的平均耗时（毫秒，包括请求往返）
+    pub avg_latency_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmbeddingBenchmark {
+    pub sample_size: usize,
+    pub provider: String,
+    pub dimensions: usize,
+    pub runs: Vec<BenchmarkRun>,
+    // 吞吐量最高的批大小，可作为 embedding_batch_size 的建议值
+    pub recommended_batch_size: usize,
+    // 建议的导入并发数：CPU 核心数的一半，至少为 1
+    pub recommended_concurrency: usize,
+}
+
+// 生成固定的测试文本：内容确定、长度接近一个切片，结果在不同机器之间可比
+fn synthetic_texts(count: usize, length: usize) -> Vec<String> {
+    let words: Vec<&str> = WORDS.split(' ').collect();
+    (0..count)
+        .map(|i| {
+            let mut text = format!("样本 {}：", i);
+            let mut word = i;
+            while text.chars().count() < length {
+                text.push_str(words[word % words.len()]);
+                text.push(' ');
+                word = word.wrapping_mul(31).wrapping_add(7);
+            }
+            text
+        })
+        .collect()
+}
+
+async fn embed_batch(client: &BackendClient, texts: &[String]) -> Result<BatchResult, String> {
+    let response = http::send(
+        client
+            .post("/api/v1/settings/embedding/benchmark")
+            .timeout(BATCH_TIMEOUT)
+            .json(&json!({ "texts": texts })),
+        true,
+    )
+    .await
+    .map_err(|e| format!("连接后端失败: {}", e))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("后端返回错误状态 {}: {}", status, body));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("解析后端响应失败: {}", e))
+}
+
+// 测试当前机器上的嵌入速度：用 sample_size 条合成文本按不同批大小向量化，
+// 只计算向量，不写入文档库或索引
+#[tauri::command]
+pub async fn run_embedding_benchmark(
+    client: State<'_, BackendClient>,
+    config: State<'_, ConfigState>,
+    sample_size: usize,
+) -> Result<EmbeddingBenchmark, String> {
+    if !(1..=MAX_SAMPLE_SIZE).contains(&sample_size) {
+        return Err(format!("sample_size 必须在 1 ~ {} 之间", MAX_SAMPLE_SIZE));
+    }
+    let config = config.get();
+    let texts = synthetic_texts(sample_size, config.chunk_size as usize);
+
+    // 先向量化一条文本，排除首次加载模型的耗时
+    let warmup = embed_batch(&client, &texts[..1]).await?;
+    log::info!("开始嵌入速度测试：{} 条文本", sample_size);
+
+    let mut runs = Vec::new();
+    let mut batch_sizes: Vec<usize> = BATCH_SIZES
+        .iter()
+        .copied()
+        .filter(|&size| size < sample_size)
+        .collect();
+    batch_sizes.push(sample_size.min(*BATCH_SIZES.last().unwrap()));
+    batch_sizes.dedup();
+    for batch_size in batch_sizes {
+        let started = Instant::now();
+        let mut batches = 0;
+        let mut embedded = 0;
+        for batch in texts.chunks(batch_size) {
+            embedded += embed_batch(&client, batch).await?.count;
+            batches += 1;
+        }
+        let elapsed = started.elapsed().as_secs_f64();
+        let run = BenchmarkRun {
+            batch_size,
+            embeddings_per_sec: embedded as f64 / elapsed.max(f64::EPSILON),
+            avg_latency_ms: elapsed * 1000.0 / batches as f64,
+        };
+        log::info!(
+            "批大小 {}：每秒 {:.1} 条，每批平均 {:.0} ms",
+            run.batch_size,
+            run.embeddings_per_sec,
+            run.avg_latency_ms
+        );
+        runs.push(run);
+    }
+
+    let recommended_batch_size = runs
+        .iter()
+        .max_by(|a, b| a.embeddings_per_sec.total_cmp(&b.embeddings_per_sec))
+        .map(|run| run.batch_size)
+        .unwrap_or(sample_size);
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    Ok(EmbeddingBenchmark {
+        sample_size,
+        provider: config.embedding_provider.as_str().to_string(),
+        dimensions: warmup.dimensions,
+        runs,
+        recommended_batch_size,
+        recommended_concurrency: (cores / 2).max(1),
+    })
+}
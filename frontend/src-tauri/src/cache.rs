@@ -0,0 +1,147 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+
+use crate::config::{AppConfig, ConfigState};
+use crate::index::IndexMaintenance;
+use crate::paths::{dir_size, AppPaths};
+use crate::vault;
+
+// 默认缓存目录（位于数据目录下）
+const CACHE_DIR_NAME: &str = "cache";
+// 预热时每个文件最多读取的字节数
+const PREWARM_READ_BYTES: u64 = 64 * 1024 * 1024;
+
+// 后端的磁盘缓存目录：未配置时使用数据目录下的 cache
+pub fn cache_dir(paths: &AppPaths, config: &AppConfig) -> PathBuf {
+    config
+        .cache_dir
+        .clone()
+        .unwrap_or_else(|| paths.data_dir.join(CACHE_DIR_NAME))
+}
+
+// a 是否是 b 本身或 b 的上级目录
+fn contains(a: &Path, b: &Path) -> bool {
+    b.starts_with(a)
+}
+
+// 清理缓存会删除目录中的全部内容，拒绝可能误删重要数据的目录
+pub fn check_cache_dir(paths: &AppPaths, dir: &Path) -> Result<(), String> {
+    if !dir.is_absolute() || dir.parent().is_none() {
+        return Err(format!(
+            "缓存目录必须是绝对路径且不能是根目录: {}",
+            dir.display()
+        ));
+    }
+    if let Some(home) = dirs::home_dir() {
+        if contains(dir, &home) {
+            return Err(format!(
+                "缓存目录不能是用户主目录或其上级目录: {}",
+                dir.display()
+            ));
+        }
+    }
+    // 数据目录下可以存放缓存，但缓存目录不能是数据目录本身或其上级目录
+    if contains(dir, &paths.data_dir) {
+        return Err(format!(
+            "缓存目录不能是数据目录或其上级目录: {}",
+            dir.display()
+        ));
+    }
+    // 清理缓存绝不能删到日志和文档库
+    for protected in [
+        &paths.log_dir,
+        &paths.default_vault_dir(),
+        &paths.vault_dir(),
+    ] {
+        if contains(dir, protected) || contains(protected, dir) {
+            return Err(format!(
+                "缓存目录不能与日志目录或文档库重叠: {}",
+                dir.display()
+            ));
+        }
+    }
+    Ok(())
+}
+
+// 依次读取缓存中已有的文件，把它们载入系统的文件缓存
+fn prewarm(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut bytes = 0;
+    let mut buffer = vec![0u8; 1024 * 1024];
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            bytes += prewarm(&entry.path());
+        } else if file_type.is_file() {
+            let Ok(file) = std::fs::File::open(entry.path()) else {
+                continue;
+            };
+            let mut reader = file.take(PREWARM_READ_BYTES);
+            while let Ok(n) = reader.read(&mut buffer) {
+                if n == 0 {
+                    break;
+                }
+                bytes += n as u64;
+            }
+        }
+    }
+    bytes
+}
+
+// 启动时确保缓存目录存在；开启 prewarm_cache 时在后台预热
+pub fn prepare(app: &AppHandle) {
+    let config = app.state::<ConfigState>().get();
+    let paths = app.state::<AppPaths>();
+    let dir = cache_dir(&paths, &config);
+    if let Err(e) = check_cache_dir(&paths, &dir) {
+        log::error!("{}", e);
+        return;
+    }
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        log::warn!("创建缓存目录 {:?} 失败: {}", dir, e);
+        return;
+    }
+    if config.prewarm_cache {
+        tauri::async_runtime::spawn_blocking(move || {
+            let bytes = prewarm(&dir);
+            log::info!("缓存目录预热完成，共读取 {} 字节", bytes);
+        });
+    }
+}
+
+// 删除缓存目录中的全部内容（不会删除缓存目录本身和文档库），期间停止后端
+#[tauri::command]
+pub async fn clear_cache(
+    app: AppHandle,
+    config: State<'_, ConfigState>,
+    paths: State<'_, AppPaths>,
+    maintenance: State<'_, IndexMaintenance>,
+) -> Result<u64, String> {
+    let dir = cache_dir(&paths, &config.get());
+    check_cache_dir(&paths, &dir)?;
+    let _guard = maintenance.begin("清理缓存")?;
+
+    let freed = vault::with_backend_stopped(&app, move || {
+        let freed = dir_size(&dir);
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            return Ok(0);
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let result = match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => std::fs::remove_dir_all(&path),
+                _ => std::fs::remove_file(&path),
+            };
+            result.map_err(|e| format!("删除缓存 {:?} 失败: {}", path, e))?;
+        }
+        Ok(freed)
+    })
+    .await?;
+    log::info!("已清理缓存，释放 {} 字节", freed);
+    Ok(freed)
+}
@@ -0,0 +1,67 @@
+use serde::Serialize;
+use tauri::AppHandle;
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use crate::deeplink;
+
+// 当前构建和平台实际可用的系统集成，前端据此隐藏无效的设置项
+#[derive(Debug, Serialize)]
+pub struct PlatformCapabilities {
+    // 系统托盘（未启用 tauri 的 tray-icon 功能）
+    pub tray: bool,
+    // 全局快捷键（未集成 global-shortcut 插件）
+    pub global_shortcuts: bool,
+    // 开机自启（未集成 autostart 插件）
+    pub autostart: bool,
+    // 文件关联（打包配置中声明了 fileAssociations 时才有）
+    pub file_associations: bool,
+    // documentqa:// 深度链接是否已注册
+    pub deep_links: bool,
+    // 能否在文件管理器中显示文档
+    pub reveal_in_file_manager: bool,
+}
+
+// PATH 中是否存在指定的可执行文件
+#[cfg(target_os = "linux")]
+fn in_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+        .unwrap_or(false)
+}
+
+fn deep_links_registered(app: &AppHandle) -> bool {
+    // Linux 和 Windows 在运行时注册，可以直接查询；macOS 由 Info.plist 声明
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    return app
+        .deep_link()
+        .is_registered(deeplink::SCHEME)
+        .unwrap_or(false);
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    {
+        let _ = (app, deeplink::SCHEME);
+        true
+    }
+}
+
+// reveal_document 在 Linux 上依赖 dbus-send、xdg-open 或 gio
+fn can_reveal() -> bool {
+    #[cfg(target_os = "linux")]
+    return ["dbus-send", "xdg-open", "gio"]
+        .iter()
+        .any(|program| in_path(program));
+    #[cfg(not(target_os = "linux"))]
+    true
+}
+
+#[tauri::command]
+pub fn get_platform_capabilities(app: AppHandle) -> PlatformCapabilities {
+    PlatformCapabilities {
+        tray: false,
+        global_shortcuts: false,
+        autostart: false,
+        file_associations: app.config().bundle.file_associations.is_some(),
+        deep_links: deep_links_registered(&app),
+        reveal_in_file_manager: can_reveal(),
+    }
+}
@@ -0,0 +1,58 @@
+use crate::config::AppConfig;
+
+// 根据配置生成 systemd 资源限制属性（MemoryMax、CPUQuota），未设置限制时为空
+pub fn resource_limits(config: &AppConfig) -> Vec<String> {
+    let mut limits = Vec::new();
+    if let Some(mb) = config.backend_mem_limit_mb {
+        limits.push(format!("MemoryMax={}M", mb));
+    }
+    if let Some(quota) = config.backend_cpu_quota {
+        limits.push(format!("CPUQuota={}%", quota));
+    }
+    if !limits.is_empty() && !cfg!(target_os = "linux") {
+        log::warn!("后端资源限制仅支持 Linux，已忽略: {}", limits.join(", "));
+        limits.clear();
+    }
+    limits
+}
+
+// 当前用户的 systemd 实例能否创建临时 scope（没有 systemd、没有用户会话或 cgroup 未委派时失败）
+#[cfg(target_os = "linux")]
+fn systemd_run_available() -> bool {
+    std::process::Command::new("systemd-run")
+        .args(["--user", "--scope", "--quiet", "true"])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+// 生成在临时 scope 中运行 program 的 systemd-run 参数；systemd 不可用时返回 None，
+// 由调用方直接启动。--scope 模式下 systemd-run 会 exec 目标程序，进程号保持不变
+#[cfg(target_os = "linux")]
+pub fn scope_args(limits: &[String], program: &str, args: &[String]) -> Option<Vec<String>> {
+    if limits.is_empty() {
+        return None;
+    }
+    if !systemd_run_available() {
+        log::warn!(
+            "systemd-run 不可用，后端将不受资源限制运行: {}",
+            limits.join(", ")
+        );
+        return None;
+    }
+    let mut scope = vec![
+        "--user".to_string(),
+        "--scope".to_string(),
+        "--quiet".to_string(),
+        "--collect".to_string(),
+    ];
+    for limit in limits {
+        scope.push("--property".to_string());
+        scope.push(limit.clone());
+    }
+    scope.push("--".to_string());
+    scope.push(program.to_string());
+    scope.extend(args.iter().cloned());
+    Some(scope)
+}
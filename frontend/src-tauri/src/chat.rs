@@ -0,0 +1,172 @@
+use futures_util::future::Abortable;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::config::ConfigState;
+use crate::http::{self, read_sse_events, BackendClient};
+use crate::metrics::{self, QueryMetric};
+use crate::presets::apply_preset;
+use crate::requests::{PendingRequests, RequestLimiter};
+
+// 流式提问：逐段转发后端 SSE 输出为 chat-token 事件，结束时发送 chat-done。
+// 调用 cancel_request(request_id) 会中止该 future，从而断开与后端的流式连接。
+// 指定 preset 时把对应预设的参数合并到请求中
+#[tauri::command]
+pub async fn stream_query(
+    app: AppHandle,
+    config: State<'_, ConfigState>,
+    pending: State<'_, PendingRequests>,
+    limiter: State<'_, RequestLimiter>,
+    query: String,
+    request_id: String,
+    preset: Option<String>,
+) -> Result<(), String> {
+    let mut body = json!({ "question": query });
+    apply_preset(&config, preset.as_deref(), &mut body)?;
+    let registration = pending.register(&request_id)?;
+    let mut metric = QueryMetric::start("stream", preset);
+    // 先登记再排队等待名额，排队中的提问同样可以被取消
+    let stream = async {
+        let _permit = limiter.acquire().await?;
+        forward_stream(&app, &body, &request_id, &mut metric).await
+    };
+    let result = Abortable::new(stream, registration).await;
+    pending.finish(&request_id);
+    metric.finish(match result {
+        Ok(Ok(())) => "ok",
+        Ok(Err(_)) => "error",
+        Err(_) => "cancelled",
+    });
+    metrics::record(&app, metric, &query);
+
+    match result {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(message)) => {
+            log::error!("流式请求 {} 失败: {}", request_id, message);
+            let _ = app.emit(
+                "chat-error",
+                json!({ "request_id": request_id, "message": message }),
+            );
+            Err(message)
+        }
+        Err(_) => {
+            log::info!("流式请求 {} 已取消", request_id);
+            Ok(())
+        }
+    }
+}
+
+async fn forward_stream(
+    app: &AppHandle,
+    body: &Value,
+    request_id: &str,
+    metric: &mut QueryMetric,
+) -> Result<(), String> {
+    let client = app.state::<BackendClient>();
+    let response = http::send(client.post("/api/v1/chat/stream").json(body), true)
+        .await
+        .map_err(|e| format!("连接后端失败: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("后端返回错误状态: {}", response.status()));
+    }
+
+    read_sse_events(response, |event| {
+        handle_event(app, request_id, event, metric)
+    })
+    .await
+}
+
+// 处理单个 SSE 事件，返回 true 表示流已正常结束
+fn handle_event(
+    app: &AppHandle,
+    request_id: &str,
+    event: Value,
+    metric: &mut QueryMetric,
+) -> Result<bool, String> {
+    match event["type"].as_str() {
+        Some("chunk") => {
+            metric.mark_first_token();
+            let _ = app.emit(
+                "chat-token",
+                json!({ "request_id": request_id, "content": event["content"] }),
+            );
+        }
+        Some("sources") => {
+            metric.set_sources(&event["sources"]);
+            let _ = app.emit(
+                "chat-sources",
+                json!({ "request_id": request_id, "sources": event["sources"] }),
+            );
+        }
+        Some("done") => {
+            metric.set_response(&event);
+            let _ = app.emit(
+                "chat-done",
+                json!({ "request_id": request_id, "data": event }),
+            );
+            return Ok(true);
+        }
+        Some("error") => {
+            let message = event["message"].as_str().unwrap_or("后端处理失败");
+            return Err(message.to_string());
+        }
+        _ => {}
+    }
+    Ok(false)
+}
+
+// 一次提问的耗时分布（毫秒）；后端未返回分项耗时时只有总耗时
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyBreakdown {
+    pub retrieval_ms: Option<u64>,
+    pub generation_ms: Option<u64>,
+    pub total_ms: u64,
+}
+
+async fn send_query(client: &BackendClient, request: &Value) -> Result<Value, String> {
+    let response = http::send(client.post("/api/v1/chat").json(request), true)
+        .await
+        .map_err(|e| format!("连接后端失败: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("后端返回错误状态: {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("解析后端响应失败: {}", e))
+}
+
+// 发送一次提问并测量端到端耗时，用于诊断界面分析时间花在了检索还是生成上
+#[tauri::command]
+pub async fn timed_query(
+    app: AppHandle,
+    client: State<'_, BackendClient>,
+    config: State<'_, ConfigState>,
+    limiter: State<'_, RequestLimiter>,
+    query: String,
+    preset: Option<String>,
+) -> Result<LatencyBreakdown, String> {
+    let mut request = json!({ "question": query });
+    apply_preset(&config, preset.as_deref(), &mut request)?;
+    let _permit = limiter.acquire().await?;
+    let mut metric = QueryMetric::start("timed", preset);
+    let started = Instant::now();
+    let result = send_query(&client, &request).await;
+    let total_ms = started.elapsed().as_millis() as u64;
+    if let Ok(body) = &result {
+        metric.set_sources(&body["sources"]);
+        metric.set_response(body);
+    }
+    metric.finish(if result.is_ok() { "ok" } else { "error" });
+    metrics::record(&app, metric, &query);
+    let body = result?;
+
+    let timing = &body["timing"];
+    Ok(LatencyBreakdown {
+        retrieval_ms: timing["retrieval_ms"].as_u64(),
+        generation_ms: timing["generation_ms"].as_u64(),
+        total_ms,
+    })
+}
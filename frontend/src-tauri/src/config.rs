@@ -0,0 +1,756 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+use crate::backend;
+use crate::backup;
+use crate::cache;
+use crate::credentials;
+use crate::http::{self, BackendClient, DEFAULT_BACKEND_HOST};
+use crate::index;
+use crate::paths::AppPaths;
+use crate::presets::QueryParams;
+use crate::requests::RequestLimiter;
+use crate::sidecar;
+use crate::webhook;
+
+// 配置文件名（位于数据目录下）
+const CONFIG_FILE: &str = "config.json";
+
+// 嵌入批大小：默认 32，允许 1 ~ 1024
+pub const DEFAULT_EMBEDDING_BATCH_SIZE: u32 = 32;
+const MAX_EMBEDDING_BATCH_SIZE: u32 = 1024;
+// 并发摄取任务数：默认 2，允许 1 ~ 32
+pub const DEFAULT_INGEST_CONCURRENCY: u32 = 2;
+const MAX_INGEST_CONCURRENCY: u32 = 32;
+
+// 文本切片大小：默认 1000（与后端默认值一致），允许 100 ~ 8000；重叠必须小于切片大小
+pub const DEFAULT_CHUNK_SIZE: u32 = 1000;
+pub const DEFAULT_CHUNK_OVERLAP: u32 = 200;
+const MIN_CHUNK_SIZE: u32 = 100;
+const MAX_CHUNK_SIZE: u32 = 8000;
+
+// 同时发往后端的最大请求数：默认 8
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
+// 请求排队的默认超时时间（秒）
+pub const DEFAULT_QUEUE_TIMEOUT_SECS: u64 = 30;
+// 索引期间系统可用内存低于该值（MB）时发出 low-memory 警告
+pub const DEFAULT_LOW_MEMORY_THRESHOLD_MB: u64 = 512;
+
+// 后端内存上限（仅 Linux）不得低于该值（MB），过低时后端无法加载模型
+const MIN_BACKEND_MEM_LIMIT_MB: u64 = 256;
+
+// 自动备份默认保留的份数
+pub const DEFAULT_BACKUP_RETENTION: u32 = 7;
+
+// 嵌入模型的来源
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbeddingProvider {
+    // 后端内置的本地模型，文档内容不离开本机
+    Local,
+    // OpenAI 兼容接口（需要 API 密钥；未配置密钥时后端回退到本地模型）
+    #[default]
+    Openai,
+}
+
+impl EmbeddingProvider {
+    // 传给后端 EMBEDDING_PROVIDER 环境变量的值
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EmbeddingProvider::Local => "local",
+            EmbeddingProvider::Openai => "openai",
+        }
+    }
+
+    // 是否通过远程 API 计算嵌入（需要凭据）
+    pub fn requires_api_key(self) -> bool {
+        !matches!(self, EmbeddingProvider::Local)
+    }
+}
+
+// 自动备份的频率
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupSchedule {
+    #[default]
+    Off,
+    Daily,
+    Weekly,
+}
+
+impl BackupSchedule {
+    // 两次自动备份之间的间隔，关闭时为 None
+    pub fn interval(self) -> Option<std::time::Duration> {
+        const DAY_SECS: u64 = 24 * 60 * 60;
+        match self {
+            BackupSchedule::Off => None,
+            BackupSchedule::Daily => Some(std::time::Duration::from_secs(DAY_SECS)),
+            BackupSchedule::Weekly => Some(std::time::Duration::from_secs(7 * DAY_SECS)),
+        }
+    }
+}
+
+// 应用配置（缺失的字段使用默认值，便于旧配置文件平滑升级）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub embedding_batch_size: u32,
+    pub ingest_concurrency: u32,
+    // 后端监听的地址，默认只监听本机
+    pub backend_host: String,
+    // 日志目录，未设置时使用数据目录下的 logs 子目录
+    pub log_dir: Option<PathBuf>,
+    // 后端工作目录（临时文件等），未设置时使用数据目录
+    pub backend_work_dir: Option<PathBuf>,
+    // 追加到后端启动参数末尾的额外参数
+    pub backend_extra_args: Vec<String>,
+    // 后端常驻内存上限（MB），持续超过时自动重启；未设置则不监控
+    pub backend_max_rss_mb: Option<u64>,
+    // 同时发往后端的最大请求数，修改后立即生效（进行中的请求不受影响）
+    pub max_concurrent_requests: usize,
+    // 达到上限时排队等待（true）还是直接返回 TooManyRequests（false）
+    pub queue_when_saturated: bool,
+    // 排队等待的超时时间（秒）
+    pub queue_timeout_secs: u64,
+    // 修改了需要重启后端的配置时是否自动重启（否则只发送 restart-required 事件）
+    pub restart_on_config_change: bool,
+    // 命名的提问参数预设（top_k、temperature 等），提问时可按名称引用
+    pub presets: BTreeMap<String, QueryParams>,
+    // 索引期间系统可用内存低于该值（MB）时发送 low-memory 事件，0 表示不监控
+    pub low_memory_threshold_mb: u64,
+    // 内存不足时是否临时把发往后端的并发请求降为 1，内存恢复后还原
+    pub throttle_on_low_memory: bool,
+    // 是否让后端使用 GPU（--device cuda），否则使用 CPU；修改后需要重启后端
+    pub gpu_enabled: bool,
+    // 调试用：把 backend_request 的方法、路径、状态码和耗时记录到日志文件（请求体会截断并隐去凭据）
+    pub log_http: bool,
+    // 当前使用的文档库目录，未设置时使用数据目录下的默认文档库
+    pub active_vault: Option<PathBuf>,
+    // 文本切片大小与相邻切片的重叠字符数，修改后已有的索引需要重建
+    pub chunk_size: u32,
+    pub chunk_overlap: u32,
+    // 切片参数变化后置为 true，提示用户重新建立索引
+    pub reindex_needed: bool,
+    // 仅 Linux：后端的内存上限（MB）和 CPU 配额（百分比，100 表示一个核心），
+    // 通过 systemd-run 创建的临时 scope 施加；未设置则不限制
+    pub backend_mem_limit_mb: Option<u64>,
+    pub backend_cpu_quota: Option<u32>,
+    // 嵌入模型来源，修改后已有的索引需要重建
+    pub embedding_provider: EmbeddingProvider,
+    // 发生指定事件时 POST 通知的地址（http/https），未设置则不发送
+    pub webhook_url: Option<String>,
+    // 触发 webhook 的事件
+    pub webhook_events: Vec<String>,
+    // 后端的磁盘缓存目录（模型文件等），未设置时使用数据目录下的 cache
+    pub cache_dir: Option<PathBuf>,
+    // 启动时是否预读缓存目录中的文件，加快首次建立索引
+    pub prewarm_cache: bool,
+    // 文档库大小上限（字节），导入会使文档库超出上限时拒绝导入；未设置则不限制
+    pub max_vault_bytes: Option<u64>,
+    // 以独立进程运行后端：关闭应用时不结束后端，下次启动时重新连接，模型无需重新加载
+    pub detached_backend: bool,
+    // 自定义后端可执行文件（开发或高级用途），设置后代替打包的后端；通过 set_custom_backend 检查后设置
+    pub custom_backend_path: Option<PathBuf>,
+    // 自动备份文档库的频率，默认关闭
+    pub backup_schedule: BackupSchedule,
+    // 自动备份保留的份数，超出的旧备份自动删除
+    pub backup_retention: u32,
+    // 自动备份保存的目录，未设置时使用数据目录下的 backups
+    pub backup_dir: Option<PathBuf>,
+    // 在本地 metrics.jsonl 中记录每次提问的耗时、检索切片数和 token 用量，默认关闭；不会发送到任何地方
+    pub collect_metrics: bool,
+    // 提问统计是否包含问题内容（隐私模式下始终不包含）
+    pub include_query_text: bool,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            embedding_batch_size: DEFAULT_EMBEDDING_BATCH_SIZE,
+            ingest_concurrency: DEFAULT_INGEST_CONCURRENCY,
+            backend_host: DEFAULT_BACKEND_HOST.to_string(),
+            log_dir: None,
+            backend_work_dir: None,
+            backend_extra_args: Vec::new(),
+            backend_max_rss_mb: None,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            queue_when_saturated: true,
+            queue_timeout_secs: DEFAULT_QUEUE_TIMEOUT_SECS,
+            restart_on_config_change: false,
+            presets: BTreeMap::new(),
+            low_memory_threshold_mb: DEFAULT_LOW_MEMORY_THRESHOLD_MB,
+            throttle_on_low_memory: true,
+            gpu_enabled: false,
+            log_http: false,
+            active_vault: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            chunk_overlap: DEFAULT_CHUNK_OVERLAP,
+            reindex_needed: false,
+            backend_mem_limit_mb: None,
+            backend_cpu_quota: None,
+            embedding_provider: EmbeddingProvider::default(),
+            webhook_url: None,
+            webhook_events: webhook::DEFAULT_EVENTS
+                .iter()
+                .map(|event| event.to_string())
+                .collect(),
+            cache_dir: None,
+            prewarm_cache: false,
+            max_vault_bytes: None,
+            detached_backend: false,
+            custom_backend_path: None,
+            backup_schedule: BackupSchedule::default(),
+            backup_retention: DEFAULT_BACKUP_RETENTION,
+            backup_dir: None,
+            collect_metrics: false,
+            include_query_text: false,
+        }
+    }
+}
+
+// 配置的全局状态
+pub struct ConfigState {
+    path: PathBuf,
+    current: Mutex<AppConfig>,
+}
+
+impl ConfigState {
+    // 从数据目录加载配置，文件不存在或损坏时使用默认配置
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(CONFIG_FILE);
+        let current = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                log::warn!("配置文件解析失败，使用默认配置: {}", e);
+                AppConfig::default()
+            }),
+            Err(_) => AppConfig::default(),
+        };
+        Self {
+            path,
+            current: Mutex::new(current),
+        }
+    }
+
+    pub fn get(&self) -> AppConfig {
+        self.current.lock().unwrap().clone()
+    }
+
+    // 保存配置：先写临时文件再重命名，避免写入中途崩溃留下半个文件
+    pub fn set(&self, config: AppConfig) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, text).map_err(|e| format!("写入配置失败: {}", e))?;
+        std::fs::rename(&tmp, &self.path).map_err(|e| format!("保存配置失败: {}", e))?;
+        *self.current.lock().unwrap() = config;
+        Ok(())
+    }
+}
+
+// 性能相关的配置项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceOptions {
+    pub embedding_batch_size: u32,
+    pub ingest_concurrency: u32,
+}
+
+impl PerformanceOptions {
+    fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_EMBEDDING_BATCH_SIZE).contains(&self.embedding_batch_size) {
+            return Err(format!(
+                "embedding_batch_size 必须在 1 ~ {} 之间",
+                MAX_EMBEDDING_BATCH_SIZE
+            ));
+        }
+        if !(1..=MAX_INGEST_CONCURRENCY).contains(&self.ingest_concurrency) {
+            return Err(format!(
+                "ingest_concurrency 必须在 1 ~ {} 之间",
+                MAX_INGEST_CONCURRENCY
+            ));
+        }
+        Ok(())
+    }
+}
+
+// 文本切片参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkingOptions {
+    pub chunk_size: u32,
+    pub chunk_overlap: u32,
+}
+
+impl ChunkingOptions {
+    fn validate(&self) -> Result<(), String> {
+        if !(MIN_CHUNK_SIZE..=MAX_CHUNK_SIZE).contains(&self.chunk_size) {
+            return Err(format!(
+                "chunk_size 必须在 {} ~ {} 之间",
+                MIN_CHUNK_SIZE, MAX_CHUNK_SIZE
+            ));
+        }
+        if self.chunk_overlap >= self.chunk_size {
+            return Err("chunk_overlap 必须小于 chunk_size".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub fn get_config(config: State<'_, ConfigState>) -> AppConfig {
+    config.get()
+}
+
+// 更新性能参数并重启后端使其生效
+#[tauri::command]
+pub async fn set_performance_options(
+    app: AppHandle,
+    config: State<'_, ConfigState>,
+    options: PerformanceOptions,
+) -> Result<(), String> {
+    options.validate()?;
+    let mut next = config.get();
+    next.embedding_batch_size = options.embedding_batch_size;
+    next.ingest_concurrency = options.ingest_concurrency;
+    config.set(next)?;
+    log::info!("性能参数已更新: {:?}", options);
+    backend::restart_backend(&app).await
+}
+
+#[derive(Debug, Serialize)]
+pub struct Chunking {
+    pub chunk_size: u32,
+    pub chunk_overlap: u32,
+    pub reindex_needed: bool,
+}
+
+#[tauri::command]
+pub fn get_chunking(config: State<'_, ConfigState>) -> Chunking {
+    let current = config.get();
+    Chunking {
+        chunk_size: current.chunk_size,
+        chunk_overlap: current.chunk_overlap,
+        reindex_needed: current.reindex_needed,
+    }
+}
+
+// 更新切片参数并重启后端。已有文档是按旧参数切片的，参数变化时标记需要重建索引
+#[tauri::command]
+pub async fn set_chunking(
+    app: AppHandle,
+    config: State<'_, ConfigState>,
+    options: ChunkingOptions,
+) -> Result<(), String> {
+    options.validate()?;
+    let mut next = config.get();
+    if next.chunk_size == options.chunk_size && next.chunk_overlap == options.chunk_overlap {
+        return Ok(());
+    }
+    next.chunk_size = options.chunk_size;
+    next.chunk_overlap = options.chunk_overlap;
+    next.reindex_needed = true;
+    config.set(next)?;
+    log::info!("切片参数已更新: {:?}，需要重建索引", options);
+    let _ = app.emit("reindex-needed", &options);
+    backend::restart_backend(&app).await
+}
+
+// 切换嵌入模型来源并重启后端。使用远程 API 时要求已配置 API 密钥；
+// 不同来源的向量互不兼容，切换后标记需要重建索引
+#[tauri::command]
+pub async fn set_embedding_provider(
+    app: AppHandle,
+    config: State<'_, ConfigState>,
+    provider: EmbeddingProvider,
+) -> Result<(), String> {
+    let mut next = config.get();
+    if next.embedding_provider == provider {
+        return Ok(());
+    }
+    if provider.requires_api_key() && !credentials::api_key_configured(&app) {
+        return Err(format!(
+            "使用 {} 嵌入需要先配置 API 密钥",
+            provider.as_str()
+        ));
+    }
+    next.embedding_provider = provider;
+    next.reindex_needed = true;
+    config.set(next)?;
+    log::warn!(
+        "嵌入模型来源已切换为 {}，已有的向量失效，需要重建索引",
+        provider.as_str()
+    );
+    let _ = app.emit(
+        "reindex-needed",
+        serde_json::json!({
+            "embedding_provider": provider,
+            "message": "切换嵌入模型来源后已有的向量失效，请重新建立索引",
+        }),
+    );
+    backend::restart_backend(&app).await
+}
+
+// 全量重建索引后清除“需要重建索引”标记，并记录索引所用的嵌入模型
+#[tauri::command]
+pub fn clear_reindex_needed(app: AppHandle, config: State<'_, ConfigState>) -> Result<(), String> {
+    index::record_index_model(&app);
+    let mut next = config.get();
+    if !next.reindex_needed {
+        return Ok(());
+    }
+    next.reindex_needed = false;
+    config.set(next)
+}
+
+// 是否监听所有网卡（会把后端暴露给局域网）
+pub fn is_wildcard_host(host: &str) -> bool {
+    host == "0.0.0.0" || host == "::"
+}
+
+fn validate_host(host: &str) -> Result<(), String> {
+    if host != "localhost" && host.parse::<IpAddr>().is_err() {
+        return Err(format!("无效的监听地址: {}", host));
+    }
+    Ok(())
+}
+
+// 监听所有网卡前弹窗让用户确认，返回用户是否同意
+fn confirm_wildcard_host(app: &AppHandle, host: &str) -> bool {
+    let confirmed = app
+        .dialog()
+        .message(format!(
+            "将后端监听地址设置为 {} 会让局域网内的其他设备也能访问后端服务及其中的文档。\n\n确定要继续吗？",
+            host
+        ))
+        .title("暴露后端到网络")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "继续".into(),
+            "取消".into(),
+        ))
+        .blocking_show();
+    if confirmed {
+        log::warn!("后端将监听 {}，局域网内的其他设备可以访问后端服务", host);
+    }
+    confirmed
+}
+
+impl AppConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        PerformanceOptions {
+            embedding_batch_size: self.embedding_batch_size,
+            ingest_concurrency: self.ingest_concurrency,
+        }
+        .validate()?;
+        ChunkingOptions {
+            chunk_size: self.chunk_size,
+            chunk_overlap: self.chunk_overlap,
+        }
+        .validate()?;
+        validate_host(&self.backend_host)?;
+        if self
+            .backend_mem_limit_mb
+            .is_some_and(|mb| mb < MIN_BACKEND_MEM_LIMIT_MB)
+        {
+            return Err(format!(
+                "backend_mem_limit_mb 不能低于 {} MB",
+                MIN_BACKEND_MEM_LIMIT_MB
+            ));
+        }
+        if self.backend_cpu_quota == Some(0) {
+            return Err("backend_cpu_quota 必须大于 0".to_string());
+        }
+        if self.backup_retention == 0 {
+            return Err("backup_retention 必须大于 0".to_string());
+        }
+        if self.max_concurrent_requests == 0 {
+            return Err("max_concurrent_requests 必须大于 0".to_string());
+        }
+        if let Some(url) = &self.webhook_url {
+            webhook::validate_url(url)?;
+        }
+        webhook::validate_events(&self.webhook_events)?;
+        for (name, params) in &self.presets {
+            params
+                .validate()
+                .map_err(|e| format!("提问预设 {}: {}", name, e))?;
+        }
+        backend::validate_extra_args(&self.backend_extra_args)
+    }
+}
+
+// 修改某个配置项后是否需要重启后端才能生效。
+// 只影响应用自身的配置项可以立即生效；未列出的配置项保守地认为需要重启
+fn requires_restart(field: &str) -> bool {
+    !matches!(
+        field,
+        "log_dir"
+            | "backend_max_rss_mb"
+            | "max_concurrent_requests"
+            | "queue_when_saturated"
+            | "queue_timeout_secs"
+            | "restart_on_config_change"
+            | "presets"
+            | "low_memory_threshold_mb"
+            | "throttle_on_low_memory"
+            | "log_http"
+            | "reindex_needed"
+            | "webhook_url"
+            | "webhook_events"
+            | "prewarm_cache"
+            | "backup_schedule"
+            | "backup_retention"
+            | "backup_dir"
+            | "collect_metrics"
+            | "include_query_text"
+    )
+}
+
+// 修改某个配置项后是否需要重新启动应用才能生效：日志目录在启动时确定，
+// BackendLogs 和 AppPaths 不会在运行中切换到新目录
+fn requires_relaunch(field: &str) -> bool {
+    field == "log_dir"
+}
+
+// 比较两份配置，返回发生变化的配置项名称
+fn changed_fields(old: &AppConfig, new: &AppConfig) -> Vec<String> {
+    let (Ok(Value::Object(old)), Ok(Value::Object(new))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return Vec::new();
+    };
+    new.iter()
+        .filter(|(key, value)| old.get(key.as_str()) != Some(value))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+// set_config 的结果
+#[derive(Debug, Serialize)]
+pub struct ConfigChange {
+    pub changed: Vec<String>,
+    pub restart_required: bool,
+    pub restarted: bool,
+    // 有配置项需要重新启动应用（relaunch）才能生效
+    pub relaunch_required: bool,
+}
+
+// 保存完整配置。只有修改了影响后端的配置项时才需要重启后端：
+// 开启 restart_on_config_change 时自动重启，否则发送 restart-required 事件由界面提示
+#[tauri::command]
+pub async fn set_config(
+    app: AppHandle,
+    config: State<'_, ConfigState>,
+    client: State<'_, BackendClient>,
+    mut new_config: AppConfig,
+) -> Result<ConfigChange, String> {
+    new_config.validate()?;
+    if let Some(dir) = &new_config.cache_dir {
+        cache::check_cache_dir(&app.state::<AppPaths>(), dir)?;
+    }
+    if let Some(dir) = &new_config.backup_dir {
+        backup::check_backup_dir(&app.state::<AppPaths>(), dir)?;
+    }
+    let old = config.get();
+    if new_config.custom_backend_path != old.custom_backend_path {
+        if let Some(path) = &new_config.custom_backend_path {
+            new_config.custom_backend_path = Some(sidecar::validate(path)?);
+        }
+    }
+    let changed = changed_fields(&old, &new_config);
+    if changed.is_empty() {
+        return Ok(ConfigChange {
+            changed,
+            restart_required: false,
+            restarted: false,
+            relaunch_required: false,
+        });
+    }
+
+    if new_config.backend_host != old.backend_host
+        && is_wildcard_host(&new_config.backend_host)
+        && !confirm_wildcard_host(&app, &new_config.backend_host)
+    {
+        return Err("已取消修改后端监听地址".to_string());
+    }
+
+    // 切片参数或嵌入模型来源变化后已有的索引需要重建
+    if changed.iter().any(|field| {
+        field == "chunk_size" || field == "chunk_overlap" || field == "embedding_provider"
+    }) {
+        new_config.reindex_needed = true;
+    }
+    let auto_restart = new_config.restart_on_config_change;
+    client.set_host(&new_config.backend_host);
+    app.state::<AppPaths>()
+        .set_active_vault(new_config.active_vault.clone());
+    app.state::<RequestLimiter>().reconfigure(&new_config);
+    config.set(new_config)?;
+    log::info!("配置已更新: {:?}", changed);
+
+    let restart_fields: Vec<&String> = changed.iter().filter(|f| requires_restart(f)).collect();
+    let restart_required = !restart_fields.is_empty();
+    let mut restarted = false;
+    if restart_required {
+        if auto_restart {
+            backend::restart_backend(&app).await?;
+            restarted = true;
+        } else {
+            let _ = app.emit("restart-required", &restart_fields);
+        }
+    }
+
+    let relaunch_fields: Vec<&String> = changed.iter().filter(|f| requires_relaunch(f)).collect();
+    let relaunch_required = !relaunch_fields.is_empty();
+    if relaunch_required {
+        log::info!("{:?} 在重新启动应用后生效", relaunch_fields);
+        let _ = app.emit("relaunch-required", &relaunch_fields);
+    }
+
+    Ok(ConfigChange {
+        changed,
+        restart_required,
+        restarted,
+        relaunch_required,
+    })
+}
+
+// 修改后端监听地址并重启后端。监听所有网卡时需要用户确认，返回是否已应用
+#[tauri::command]
+pub async fn set_backend_host(
+    app: AppHandle,
+    config: State<'_, ConfigState>,
+    client: State<'_, BackendClient>,
+    host: String,
+) -> Result<bool, String> {
+    let host = host.trim().to_string();
+    validate_host(&host)?;
+    if is_wildcard_host(&host) && !confirm_wildcard_host(&app, &host) {
+        return Ok(false);
+    }
+
+    let mut next = config.get();
+    next.backend_host = host.clone();
+    config.set(next)?;
+    client.set_host(&host);
+    log::info!("后端监听地址已更新为 {}", host);
+    backend::restart_backend(&app).await?;
+    Ok(true)
+}
+
+// 后端实际加载的配置；后端不提供 /config 接口时 supported 为 false
+#[derive(Debug, Serialize)]
+pub struct BackendEffectiveConfig {
+    pub supported: bool,
+    pub settings: Option<Value>,
+}
+
+// 查询后端实际生效的配置，便于对比请求的配置与后端加载的配置
+#[tauri::command]
+pub async fn get_backend_effective_config(
+    client: State<'_, BackendClient>,
+) -> Result<BackendEffectiveConfig, String> {
+    let response = http::send(client.get("/api/v1/config"), true)
+        .await
+        .map_err(|e| format!("连接后端失败: {}", e))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::NOT_IMPLEMENTED {
+        return Ok(BackendEffectiveConfig {
+            supported: false,
+            settings: None,
+        });
+    }
+    if !status.is_success() {
+        return Err(format!("后端返回错误状态: {}", status));
+    }
+    let settings = response
+        .json()
+        .await
+        .map_err(|e| format!("解析后端响应失败: {}", e))?;
+    Ok(BackendEffectiveConfig {
+        supported: true,
+        settings: Some(settings),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 对比时忽略顺序
+    fn sorted(mut fields: Vec<String>) -> Vec<String> {
+        fields.sort();
+        fields
+    }
+
+    #[test]
+    fn unchanged_config_has_no_changed_fields() {
+        let config = AppConfig::default();
+        assert!(changed_fields(&config, &config.clone()).is_empty());
+    }
+
+    #[test]
+    fn zero_max_concurrent_requests_is_rejected() {
+        let config = AppConfig {
+            max_concurrent_requests: 0,
+            ..AppConfig::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(AppConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn app_only_field_does_not_require_restart() {
+        let old = AppConfig::default();
+        let mut new = old.clone();
+        new.webhook_url = Some("http://127.0.0.1:9000/hook".to_string());
+        let changed = changed_fields(&old, &new);
+        assert_eq!(changed, vec!["webhook_url".to_string()]);
+        assert!(!requires_restart("webhook_url"));
+        assert!(!requires_relaunch("webhook_url"));
+        // 并发上限由 RequestLimiter::reconfigure 立即应用
+        assert!(!requires_restart("max_concurrent_requests"));
+    }
+
+    #[test]
+    fn log_dir_requires_relaunch() {
+        let old = AppConfig::default();
+        let mut new = old.clone();
+        new.log_dir = Some(PathBuf::from("/tmp/document-qa-logs"));
+        assert_eq!(changed_fields(&old, &new), vec!["log_dir".to_string()]);
+        // 不影响后端，但日志目录只在应用启动时确定
+        assert!(!requires_restart("log_dir"));
+        assert!(requires_relaunch("log_dir"));
+    }
+
+    #[test]
+    fn backend_field_requires_restart() {
+        let old = AppConfig::default();
+        let mut new = old.clone();
+        new.backend_host = "0.0.0.0".to_string();
+        assert_eq!(changed_fields(&old, &new), vec!["backend_host".to_string()]);
+        assert!(requires_restart("backend_host"));
+        assert!(requires_restart("gpu_enabled"));
+        // 未列出的配置项保守地认为需要重启
+        assert!(requires_restart("some_future_field"));
+    }
+
+    #[test]
+    fn multi_field_diff_lists_every_change() {
+        let old = AppConfig::default();
+        let mut new = old.clone();
+        new.log_dir = Some(PathBuf::from("/tmp/document-qa-logs"));
+        new.chunk_size = 500;
+        new.webhook_events.clear();
+        let changed = changed_fields(&old, &new);
+        assert_eq!(
+            sorted(changed.clone()),
+            vec!["chunk_size", "log_dir", "webhook_events"]
+        );
+        let restart: Vec<&String> = changed.iter().filter(|f| requires_restart(f)).collect();
+        assert_eq!(restart, vec!["chunk_size"]);
+    }
+}
@@ -0,0 +1,141 @@
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use zeroize::Zeroizing;
+
+use crate::backend;
+use crate::logs::{self, BackendLogs};
+use crate::paths::AppPaths;
+
+// 后端保存 LLM 配置（含 API 密钥）的文件，位于后端的数据目录（当前文档库）下
+const LLM_CONFIG_FILE: &str = "llm_config.json";
+// 需要清除的凭据字段
+const CREDENTIAL_KEYS: [&str; 1] = ["api_key"];
+
+// 从 LLM 配置中移除凭据，返回被移除的密钥（读取到的内容在释放时清零）
+fn remove_credentials(path: &Path) -> Result<Vec<Zeroizing<String>>, String> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => Zeroizing::new(text),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("读取 LLM 配置失败: {}", e)),
+    };
+    let mut config: Value =
+        serde_json::from_str(&text).map_err(|e| format!("解析 LLM 配置失败: {}", e))?;
+    let Some(object) = config.as_object_mut() else {
+        return Ok(Vec::new());
+    };
+
+    let mut secrets = Vec::new();
+    for key in CREDENTIAL_KEYS {
+        if let Some(Value::String(secret)) = object.remove(key) {
+            secrets.push(Zeroizing::new(secret));
+        }
+    }
+    // 先写临时文件再重命名，不在磁盘上留下包含密钥的旧内容
+    let rest = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, rest).map_err(|e| format!("写入 LLM 配置失败: {}", e))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("保存 LLM 配置失败: {}", e))?;
+    Ok(secrets)
+}
+
+// 后端默认写入的占位密钥，不算已配置
+const PLACEHOLDER_API_KEY: &str = "your_api_key_here";
+
+// 后端的 LLM 配置文件。后端的 DATA_DIR 总是指向当前文档库，配置文件随文档库切换
+pub fn llm_config_path(app: &AppHandle) -> PathBuf {
+    app.state::<AppPaths>().vault_dir().join(LLM_CONFIG_FILE)
+}
+
+// 当前配置的 API 密钥：与后端一致，优先读取 LLM 配置文件，其次是 OPENAI_API_KEY 环境变量
+pub fn saved_api_key(app: &AppHandle) -> Option<Zeroizing<String>> {
+    let from_file = std::fs::read_to_string(llm_config_path(app))
+        .ok()
+        .map(Zeroizing::new)
+        .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+        .and_then(|config| {
+            config["api_key"]
+                .as_str()
+                .map(|key| Zeroizing::new(key.to_string()))
+        });
+    from_file
+        .or_else(|| std::env::var("OPENAI_API_KEY").ok().map(Zeroizing::new))
+        .filter(|key| !key.is_empty() && key.as_str() != PLACEHOLDER_API_KEY)
+}
+
+// 是否配置了 API 密钥
+pub fn api_key_configured(app: &AppHandle) -> bool {
+    saved_api_key(app).is_some()
+}
+
+// 后端默认的嵌入模型（与后端 EMBEDDING_MODEL 默认值一致）
+const DEFAULT_EMBEDDING_MODEL: &str = "Qwen/Qwen3-Embedding-8B";
+
+// 后端使用远程接口时的嵌入模型：优先读取 LLM 配置文件，其次是 EMBEDDING_MODEL 环境变量
+pub fn embedding_model(app: &AppHandle) -> String {
+    std::fs::read_to_string(llm_config_path(app))
+        .ok()
+        .map(Zeroizing::new)
+        .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+        .and_then(|config| config["embedding_model"].as_str().map(str::to_string))
+        .filter(|model| !model.is_empty())
+        .or_else(|| std::env::var("EMBEDDING_MODEL").ok())
+        .unwrap_or_else(|| DEFAULT_EMBEDDING_MODEL.to_string())
+}
+
+// 清除保存的 API 密钥和会话日志（适用于共用电脑），确认后重启后端使其不再持有凭据。
+// 返回 false 表示用户取消
+#[tauri::command]
+pub async fn clear_credentials(app: AppHandle) -> Result<bool, String> {
+    let confirmed = app
+        .dialog()
+        .message("将删除保存的 API 密钥和会话日志，并从日志和崩溃报告中抹去密钥内容。之后需要重新配置才能提问。\n\n确定要继续吗？")
+        .title("清除凭据")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "清除".into(),
+            "取消".into(),
+        ))
+        .blocking_show();
+    if !confirmed {
+        return Ok(false);
+    }
+
+    let secrets = remove_credentials(&llm_config_path(&app))?;
+    for secret in &secrets {
+        logs::redact_secret(&app, secret);
+    }
+    app.state::<BackendLogs>().remove_session_files();
+    log::info!("已清除 {} 项保存的凭据和会话日志", secrets.len());
+    drop(secrets);
+
+    backend::restart_backend(&app).await?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_credentials_keeps_other_settings() {
+        let dir =
+            std::env::temp_dir().join(format!("document-qa-credentials-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(LLM_CONFIG_FILE);
+        std::fs::write(&path, r#"{"api_key": "sk-test", "model": "gpt-4o-mini"}"#).unwrap();
+
+        let secrets = remove_credentials(&path).unwrap();
+        assert_eq!(secrets.len(), 1);
+        assert_eq!(secrets[0].as_str(), "sk-test");
+        let rest: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(rest.get("api_key").is_none());
+        assert_eq!(rest["model"], "gpt-4o-mini");
+
+        // 配置文件不存在时没有需要清除的凭据
+        std::fs::remove_file(&path).unwrap();
+        assert!(remove_credentials(&path).unwrap().is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
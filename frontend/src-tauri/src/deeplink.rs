@@ -0,0 +1,112 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Listener, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
+use url::Url;
+
+// 自定义 URL scheme：documentqa://query?text=...
+pub const SCHEME: &str = "documentqa";
+// 与后端 ChatRequest.question 的长度上限一致
+const MAX_QUERY_CHARS: usize = 2000;
+
+// 后端就绪前收到的深度链接提问先暂存，就绪后再发送 external-query
+pub struct DeepLinks {
+    ready: AtomicBool,
+    pending: Mutex<Vec<String>>,
+}
+
+impl DeepLinks {
+    pub fn new() -> Self {
+        Self {
+            ready: AtomicBool::new(false),
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+// 解析深度链接，返回要提问的文本；不支持的 action 或无效参数返回错误
+fn parse_query(url: &Url) -> Result<String, String> {
+    if url.scheme() != SCHEME {
+        return Err(format!("不支持的 URL scheme: {}", url.scheme()));
+    }
+    match url.host_str() {
+        Some("query") => {}
+        action => return Err(format!("不支持的深度链接操作: {:?}", action)),
+    }
+    let text = url
+        .query_pairs()
+        .find(|(key, _)| key == "text")
+        .map(|(_, value)| value.trim().to_string())
+        .unwrap_or_default();
+    if text.is_empty() {
+        return Err("深度链接缺少 text 参数".to_string());
+    }
+    if text.chars().count() > MAX_QUERY_CHARS {
+        return Err(format!("提问内容超过 {} 个字符", MAX_QUERY_CHARS));
+    }
+    Ok(text)
+}
+
+fn emit_query(app: &AppHandle, text: String) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.set_focus();
+    }
+    let _ = app.emit("external-query", serde_json::json!({ "text": text }));
+}
+
+fn handle_url(app: &AppHandle, url: &Url) {
+    let text = match parse_query(url) {
+        Ok(text) => text,
+        Err(e) => {
+            log::warn!("忽略深度链接: {}", e);
+            return;
+        }
+    };
+    log::info!("收到深度链接提问（{} 个字符）", text.chars().count());
+    let links = app.state::<DeepLinks>();
+    if links.ready.load(Ordering::SeqCst) {
+        emit_query(app, text);
+    } else {
+        links.pending.lock().unwrap().push(text);
+    }
+}
+
+// 后端就绪后发送暂存的提问
+fn mark_ready(app: &AppHandle) {
+    let links = app.state::<DeepLinks>();
+    links.ready.store(true, Ordering::SeqCst);
+    let pending = std::mem::take(&mut *links.pending.lock().unwrap());
+    for text in pending {
+        emit_query(app, text);
+    }
+}
+
+// 注册 URL scheme 并处理启动时和运行期间收到的深度链接。
+// 应用已在运行时，single-instance 插件会把新实例收到的链接转交给这里
+pub fn init(app: &AppHandle) {
+    // Linux 和 Windows 需要在运行时注册 scheme（macOS 通过 Info.plist 注册）
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    if let Err(e) = app.deep_link().register_all() {
+        log::warn!("注册 URL scheme 失败: {}", e);
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            handle_url(&handle, &url);
+        }
+    });
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        for url in urls {
+            handle_url(app, &url);
+        }
+    }
+
+    if cfg!(debug_assertions) {
+        // 开发模式下后端由开发者自行启动，不会有 backend-ready 事件
+        mark_ready(app);
+    } else {
+        let handle = app.clone();
+        app.listen("backend-ready", move |_| mark_ready(&handle));
+    }
+}
@@ -0,0 +1,241 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+use tauri::{AppHandle, Manager};
+
+use crate::backend::{self, now_millis, BackendFailure, BackendProcess, FailureKind, SpawnPlan};
+use crate::config::ConfigState;
+use crate::http::{check_health, BackendClient, BACKEND_PORT};
+use crate::paths::AppPaths;
+use crate::processes;
+
+// 数据目录中记录独立运行的后端的文件，下次启动应用时据此重新连接
+const RECORD_FILE: &str = "detached-backend.json";
+// 独立运行的后端不再通过管道输出，stdout/stderr 写入日志目录下的该文件
+pub const DETACHED_LOG_FILE: &str = "backend-detached.log";
+// 启动时日志文件超过该大小则轮转为 backend-detached.log.1
+const MAX_LOG_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+// 独立运行的后端：进程号、端口和启动时间
+#[derive(Debug, Serialize, Deserialize)]
+struct DetachedRecord {
+    pid: u32,
+    port: u16,
+    // Unix 时间戳（毫秒）
+    started_at: u64,
+}
+
+fn record_path(app: &AppHandle) -> PathBuf {
+    app.state::<AppPaths>().data_dir.join(RECORD_FILE)
+}
+
+fn read_record(app: &AppHandle) -> Option<DetachedRecord> {
+    let text = std::fs::read_to_string(record_path(app)).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+// 先写临时文件再重命名，避免写入中途崩溃留下半个文件
+fn write_record(app: &AppHandle, record: &DetachedRecord) -> Result<(), String> {
+    let path = record_path(app);
+    let text = serde_json::to_string_pretty(record).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, text).map_err(|e| format!("写入独立后端记录失败: {}", e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("保存独立后端记录失败: {}", e))
+}
+
+fn remove_record(app: &AppHandle) {
+    let _ = std::fs::remove_file(record_path(app));
+}
+
+// 打开（必要时先轮转）独立后端的日志文件
+fn open_log_file(log_dir: &Path) -> Result<File, String> {
+    let path = log_dir.join(DETACHED_LOG_FILE);
+    if std::fs::metadata(&path).is_ok_and(|meta| meta.len() > MAX_LOG_FILE_BYTES) {
+        let _ = std::fs::rename(&path, log_dir.join(format!("{}.1", DETACHED_LOG_FILE)));
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("打开 {:?} 失败: {}", path, e))
+}
+
+// 让后端脱离应用进程：不继承控制台和进程组，应用退出时不会被一并结束
+#[cfg(unix)]
+fn detach(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+}
+
+#[cfg(windows)]
+fn detach(command: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    const DETACHED_PROCESS: u32 = 0x0000_0008;
+    const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+    command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+}
+
+#[cfg(not(any(unix, windows)))]
+fn detach(_command: &mut Command) {}
+
+// 进程存在并且确实是我们的后端（防止进程号被其他程序重用）
+fn is_backend_process(pid: u32) -> bool {
+    let target = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[target]),
+        true,
+        ProcessRefreshKind::nothing()
+            .with_exe(UpdateKind::OnlyIfNotSet)
+            .with_cmd(UpdateKind::OnlyIfNotSet),
+    );
+    system.process(target).is_some_and(processes::is_backend)
+}
+
+// 按启动方案以独立进程启动后端，返回进程号
+pub fn spawn(app: &AppHandle, plan: &SpawnPlan) -> Result<u32, String> {
+    let program = plan
+        .source
+        .program_path()
+        .ok_or("无法确定后端可执行文件路径")?;
+    if !plan.resource_limits.is_empty() {
+        log::warn!("独立运行的后端不支持资源限制，已忽略");
+    }
+    let log_file = open_log_file(&app.state::<AppPaths>().log_dir)?;
+    let stderr = log_file
+        .try_clone()
+        .map_err(|e| format!("打开独立后端日志失败: {}", e))?;
+
+    let mut command = Command::new(&program);
+    command
+        .args(&plan.args)
+        .envs(plan.env.iter().cloned())
+        .current_dir(&plan.work_dir)
+        .stdin(Stdio::null())
+        .stdout(log_file)
+        .stderr(stderr);
+    detach(&mut command);
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("启动独立后端失败: {}", e))?;
+    let pid = child.id();
+
+    write_record(
+        app,
+        &DetachedRecord {
+            pid,
+            port: BACKEND_PORT,
+            started_at: now_millis(),
+        },
+    )?;
+    // 应用运行期间回收退出的进程，避免留下僵尸进程；应用退出后后端继续运行
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) => log::info!("[Backend] 独立后端进程退出: {}", status),
+        Err(e) => log::warn!("等待独立后端退出失败: {}", e),
+    });
+    Ok(pid)
+}
+
+// 启动时重新连接上次留下的独立后端：进程仍在运行且健康检查通过时返回 true。
+// 关闭了 detached_backend 时结束遗留的后端，由应用重新启动受管理的后端
+pub async fn reattach(app: &AppHandle) -> bool {
+    let Some(record) = read_record(app) else {
+        return false;
+    };
+    if !is_backend_process(record.pid) {
+        log::info!("上次的独立后端 (pid {}) 已不在运行", record.pid);
+        remove_record(app);
+        return false;
+    }
+    if !app.state::<ConfigState>().get().detached_backend {
+        log::info!(
+            "detached_backend 已关闭，结束上次的独立后端 (pid {})",
+            record.pid
+        );
+        kill(app, record.pid);
+        return false;
+    }
+    if record.port != BACKEND_PORT || !check_health(&app.state::<BackendClient>()).await {
+        log::warn!(
+            "独立后端 (pid {}) 未响应健康检查，结束并重新启动",
+            record.pid
+        );
+        kill(app, record.pid);
+        return false;
+    }
+
+    backend::adopt_detached(app, record.pid);
+    log::info!("已重新连接独立运行的后端 (pid {})", record.pid);
+    true
+}
+
+// 结束独立后端进程并删除记录
+pub fn kill(app: &AppHandle, pid: u32) {
+    remove_record(app);
+    let target = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[target]), true);
+    match system.process(target) {
+        Some(process) if process.kill() => log::info!("独立后端已终止 (pid {})", pid),
+        Some(_) => log::error!("终止独立后端失败 (pid {})", pid),
+        None => {}
+    }
+}
+
+// 定期确认独立后端仍在运行；进程意外退出时按失败处理（无法获得退出码，输出见日志文件）
+pub async fn monitor(app: AppHandle, pid: u32) {
+    let mut system = System::new();
+    loop {
+        tokio::time::sleep(backend::LIVENESS_POLL_INTERVAL).await;
+        let state = app.state::<BackendProcess>();
+        if state.detached_pid() != Some(pid) {
+            return;
+        }
+        if !backend::process_alive(&mut system, pid) {
+            break;
+        }
+    }
+
+    let state = app.state::<BackendProcess>();
+    if state.detached_pid() != Some(pid) {
+        return;
+    }
+    state.detached.lock().unwrap().take();
+    remove_record(&app);
+    backend::report_failure(
+        &app,
+        BackendFailure {
+            exit_code: None,
+            signal: None,
+            stdout_tail: Vec::new(),
+            stderr_tail: Vec::new(),
+            timestamp: now_millis(),
+            kind: FailureKind::Crashed,
+            message: format!(
+                "独立后端进程 (pid {}) 已退出，输出见 {}",
+                pid, DETACHED_LOG_FILE
+            ),
+            memory_samples: Vec::new(),
+        },
+    );
+}
+
+// 结束独立运行的后端（关闭窗口不会结束它）。返回是否有独立后端被结束
+#[tauri::command]
+pub fn stop_detached_backend(app: AppHandle) -> bool {
+    let state = app.state::<BackendProcess>();
+    let pid = state.detached.lock().unwrap().take();
+    let pid = pid.or_else(|| read_record(&app).map(|record| record.pid));
+    match pid {
+        Some(pid) if is_backend_process(pid) => {
+            kill(&app, pid);
+            true
+        }
+        _ => {
+            remove_record(&app);
+            false
+        }
+    }
+}
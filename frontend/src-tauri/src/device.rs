@@ -0,0 +1,76 @@
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::ConfigState;
+use crate::http::{self, BackendClient};
+
+// 后端实际使用的计算设备
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendDevice {
+    // "cuda"、"cpu"，或使用远程嵌入接口时的 "remote"
+    pub device: String,
+    pub requested_gpu: bool,
+    // 后端回退到 CPU 时给出的原因
+    pub reason: Option<String>,
+}
+
+// 传给后端的 DEVICE 环境变量
+pub fn device_arg(gpu_enabled: bool) -> &'static str {
+    if gpu_enabled {
+        "cuda"
+    } else {
+        "cpu"
+    }
+}
+
+// 向后端查询模型实际加载在哪个设备上；请求了 GPU 却在 CPU 上运行时发送 gpu-unavailable 事件
+async fn query_device(app: &AppHandle) -> Result<BackendDevice, String> {
+    let requested_gpu = app.state::<ConfigState>().get().gpu_enabled;
+    let response = http::send(app.state::<BackendClient>().get("/api/v1/device"), true)
+        .await
+        .map_err(|e| format!("连接后端失败: {}", e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err("当前后端版本不支持查询计算设备".to_string());
+    }
+    if !response.status().is_success() {
+        return Err(format!("后端返回错误状态: {}", response.status()));
+    }
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("解析后端响应失败: {}", e))?;
+
+    let device = BackendDevice {
+        device: body["device"].as_str().unwrap_or("unknown").to_string(),
+        requested_gpu,
+        reason: body["reason"].as_str().map(str::to_string),
+    };
+    if requested_gpu && device.device == "cpu" {
+        let reason = device
+            .reason
+            .clone()
+            .unwrap_or_else(|| "后端未能使用 GPU".to_string());
+        log::warn!("已启用 GPU，但后端运行在 CPU 上: {}", reason);
+        let _ = app.emit("gpu-unavailable", serde_json::json!({ "reason": reason }));
+    }
+    Ok(device)
+}
+
+// 后端就绪后检查 GPU 是否生效（只在启用了 GPU 时检查）
+pub fn check_after_ready(app: &AppHandle) {
+    if !app.state::<ConfigState>().get().gpu_enabled {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = query_device(&app).await {
+            log::warn!("查询后端计算设备失败: {}", e);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_backend_device(app: AppHandle) -> Result<BackendDevice, String> {
+    query_device(&app).await
+}
@@ -0,0 +1,114 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::State;
+
+use crate::backend::BackendProcess;
+
+// 两次采样的最短间隔，间隔内的请求直接返回上一次的结果
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+// sysinfo 能读取进程磁盘读写计数的平台
+const SUPPORTED: bool = cfg!(any(
+    target_os = "linux",
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "freebsd"
+));
+
+// 后端进程的磁盘读写速率；平台不支持时 supported 为 false，速率为 None
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendIo {
+    pub supported: bool,
+    pub pid: Option<u32>,
+    pub read_bytes_per_sec: Option<u64>,
+    pub write_bytes_per_sec: Option<u64>,
+}
+
+// 上一次采样：累计读写字节数
+struct Sample {
+    pid: u32,
+    at: Instant,
+    total_read: u64,
+    total_written: u64,
+    result: BackendIo,
+}
+
+pub struct DiskIoSampler {
+    system: Mutex<System>,
+    last: Mutex<Option<Sample>>,
+}
+
+impl DiskIoSampler {
+    pub fn new() -> Self {
+        Self {
+            system: Mutex::new(System::new()),
+            last: Mutex::new(None),
+        }
+    }
+
+    fn sample(&self, pid: u32) -> Result<BackendIo, String> {
+        let mut last = self.last.lock().unwrap();
+        if let Some(sample) = last.as_ref() {
+            if sample.pid == pid && sample.at.elapsed() < SAMPLE_INTERVAL {
+                return Ok(sample.result.clone());
+            }
+        }
+
+        let mut system = self.system.lock().unwrap();
+        let target = Pid::from_u32(pid);
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[target]),
+            true,
+            ProcessRefreshKind::nothing().with_disk_usage(),
+        );
+        let usage = system.process(target).ok_or("后端进程已退出")?.disk_usage();
+        let now = Instant::now();
+
+        // 第一次采样（或后端已重启）时没有可比较的基准，速率为 None，下一次请求才有结果
+        let (read_rate, write_rate) = match last.as_ref().filter(|sample| sample.pid == pid) {
+            Some(sample) => {
+                let secs = now.duration_since(sample.at).as_secs_f64();
+                let rate =
+                    |now: u64, before: u64| (now.saturating_sub(before) as f64 / secs) as u64;
+                (
+                    Some(rate(usage.total_read_bytes, sample.total_read)),
+                    Some(rate(usage.total_written_bytes, sample.total_written)),
+                )
+            }
+            None => (None, None),
+        };
+        let result = BackendIo {
+            supported: true,
+            pid: Some(pid),
+            read_bytes_per_sec: read_rate,
+            write_bytes_per_sec: write_rate,
+        };
+        *last = Some(Sample {
+            pid,
+            at: now,
+            total_read: usage.total_read_bytes,
+            total_written: usage.total_written_bytes,
+            result: result.clone(),
+        });
+        Ok(result)
+    }
+}
+
+// 后端进程当前的磁盘读写速率（字节/秒），用于解释索引期间系统变慢的原因
+#[tauri::command]
+pub fn get_backend_io(
+    backend: State<'_, BackendProcess>,
+    sampler: State<'_, DiskIoSampler>,
+) -> Result<BackendIo, String> {
+    if !SUPPORTED {
+        return Ok(BackendIo {
+            supported: false,
+            pid: backend.pid(),
+            read_bytes_per_sec: None,
+            write_bytes_per_sec: None,
+        });
+    }
+    let pid = backend.pid().ok_or("后端未运行")?;
+    sampler.sample(pid)
+}
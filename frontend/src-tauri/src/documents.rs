@@ -0,0 +1,132 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::State;
+
+use crate::paths::AppPaths;
+use crate::recent::{RecentAction, RecentDocuments};
+
+// 运行一个外部命令，返回是否成功
+fn run(program: &str, args: &[&str]) -> Result<(), String> {
+    let status = Command::new(program)
+        .args(args)
+        .status()
+        .map_err(|e| format!("{}: {}", program, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{}: 退出状态 {}", program, status))
+    }
+}
+
+// 一种打开方式：名称和具体的执行函数
+type Attempt<'a> = (&'a str, &'a dyn Fn() -> Result<(), String>);
+
+// 依次尝试多种方式，第一个成功即返回；全部失败时汇总所有错误
+fn try_in_order(attempts: &[Attempt]) -> Result<(), String> {
+    let mut errors = Vec::new();
+    for (name, attempt) in attempts {
+        match attempt() {
+            Ok(_) => {
+                log::info!("已通过 {} 打开", name);
+                return Ok(());
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+    Err(format!("所有打开方式均失败: {}", errors.join("; ")))
+}
+
+fn existing_path(path: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(path);
+    if !path.exists() {
+        return Err(format!("文件不存在: {}", path.display()));
+    }
+    Ok(path)
+}
+
+// Linux 上通过 freedesktop FileManager1 DBus 接口在文件管理器中选中文件
+fn show_items_via_dbus(path: &Path) -> Result<(), String> {
+    let uri = url::Url::from_file_path(path)
+        .map_err(|_| format!("无法转换为文件 URI: {}", path.display()))?;
+    run(
+        "dbus-send",
+        &[
+            "--session",
+            "--print-reply",
+            "--dest=org.freedesktop.FileManager1",
+            "--type=method_call",
+            "/org/freedesktop/FileManager1",
+            "org.freedesktop.FileManager1.ShowItems",
+            &format!("array:string:{}", uri),
+            "string:",
+        ],
+    )
+}
+
+// 用系统默认程序打开文件或目录；Linux 上依次尝试 xdg-open 和 gio open
+pub fn open_with_default(path: &Path) -> Result<(), String> {
+    let target = path.to_string_lossy();
+    if cfg!(target_os = "windows") {
+        run("explorer", &[&target])
+    } else if cfg!(target_os = "macos") {
+        run("open", &[&target])
+    } else {
+        try_in_order(&[
+            ("xdg-open", &|| run("xdg-open", &[&target])),
+            ("gio open", &|| run("gio", &["open", &target])),
+        ])
+    }
+}
+
+// 在文件管理器中显示文档
+#[tauri::command]
+pub fn reveal_document(path: String) -> Result<(), String> {
+    let path = existing_path(&path)?;
+    let target = path.to_string_lossy();
+    if cfg!(target_os = "windows") {
+        // explorer 选中文件时即使成功也可能返回非零状态，这里只关心能否启动
+        Command::new("explorer")
+            .arg(format!("/select,{}", target))
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| format!("打开资源管理器失败: {}", e))
+    } else if cfg!(target_os = "macos") {
+        run("open", &["-R", &target])
+    } else {
+        // 不支持 DBus 接口的桌面环境退而打开所在目录
+        let parent = path.parent().unwrap_or(&path).to_path_buf();
+        try_in_order(&[
+            ("FileManager1 DBus", &|| show_items_via_dbus(&path)),
+            ("xdg-open", &|| {
+                run("xdg-open", &[&parent.to_string_lossy()])
+            }),
+            ("gio open", &|| {
+                run("gio", &["open", &parent.to_string_lossy()])
+            }),
+        ])
+    }
+}
+
+// 用系统默认程序打开文档，并记入最近文档（id 为后端文档 ID，缺省时按路径去重）
+#[tauri::command]
+pub fn open_document_at(
+    recent: State<'_, RecentDocuments>,
+    path: String,
+    id: Option<String>,
+) -> Result<(), String> {
+    let path = existing_path(&path)?;
+    open_with_default(&path)?;
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let id = id.unwrap_or_else(|| path.to_string_lossy().to_string());
+    recent.record(id, name, Some(path), RecentAction::Opened);
+    Ok(())
+}
+
+// 在文件管理器中打开数据目录
+#[tauri::command]
+pub fn open_data_dir(paths: State<'_, AppPaths>) -> Result<(), String> {
+    open_with_default(&paths.data_dir)
+}
@@ -0,0 +1,111 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::State;
+
+// EWMA 的平滑系数：越小越平稳，越大对最近的速度越敏感
+const SMOOTHING: f64 = 0.3;
+
+#[derive(Default)]
+struct Progress {
+    total_docs: u64,
+    total_bytes: u64,
+    done_docs: u64,
+    done_bytes: u64,
+    last_sample: Option<Instant>,
+    // 平滑后的吞吐量，至少有两个采样后才有值
+    docs_per_sec: Option<f64>,
+    bytes_per_sec: Option<f64>,
+}
+
+// 索引（导入、修复等）进度与吞吐量，由转发进度事件的地方更新
+pub struct IndexThroughput(Mutex<Option<Progress>>);
+
+fn smooth(previous: Option<f64>, sample: f64) -> f64 {
+    match previous {
+        Some(previous) => SMOOTHING * sample + (1.0 - SMOOTHING) * previous,
+        None => sample,
+    }
+}
+
+impl IndexThroughput {
+    pub fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+
+    // 开始一次新的索引任务
+    pub fn start(&self, total_docs: u64, total_bytes: u64) {
+        *self.0.lock().unwrap() = Some(Progress {
+            total_docs,
+            total_bytes,
+            last_sample: Some(Instant::now()),
+            ..Default::default()
+        });
+    }
+
+    // 记录累计完成的文档数和字节数
+    pub fn record(&self, done_docs: u64, done_bytes: u64) {
+        let mut guard = self.0.lock().unwrap();
+        let Some(progress) = guard.as_mut() else {
+            return;
+        };
+        let now = Instant::now();
+        if let Some(last) = progress.last_sample {
+            let elapsed = now.duration_since(last).as_secs_f64();
+            if elapsed > 0.0 {
+                let docs = done_docs.saturating_sub(progress.done_docs) as f64 / elapsed;
+                let bytes = done_bytes.saturating_sub(progress.done_bytes) as f64 / elapsed;
+                progress.docs_per_sec = Some(smooth(progress.docs_per_sec, docs));
+                progress.bytes_per_sec = Some(smooth(progress.bytes_per_sec, bytes));
+            }
+        }
+        progress.done_docs = done_docs;
+        progress.done_bytes = done_bytes;
+        progress.last_sample = Some(now);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.0.lock().unwrap().is_some()
+    }
+
+    pub fn finish(&self) {
+        *self.0.lock().unwrap() = None;
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct IndexEta {
+    pub done_docs: u64,
+    pub total_docs: u64,
+    pub docs_per_sec: f64,
+    pub bytes_per_sec: f64,
+    pub remaining_secs: u64,
+}
+
+// 估算当前索引任务的剩余时间；没有进行中的任务或采样不足时返回 None
+#[tauri::command]
+pub fn get_index_eta(throughput: State<'_, IndexThroughput>) -> Option<IndexEta> {
+    let guard = throughput.0.lock().unwrap();
+    let progress = guard.as_ref()?;
+    let docs_per_sec = progress.docs_per_sec?;
+    let bytes_per_sec = progress.bytes_per_sec.unwrap_or(0.0);
+
+    // 知道总字节数时按字节估算（文件大小差异大时更准确），否则按文档数
+    let remaining_bytes = progress.total_bytes.saturating_sub(progress.done_bytes);
+    let remaining_docs = progress.total_docs.saturating_sub(progress.done_docs);
+    let remaining_secs = if progress.total_bytes > 0 && bytes_per_sec > 0.0 {
+        remaining_bytes as f64 / bytes_per_sec
+    } else if docs_per_sec > 0.0 {
+        remaining_docs as f64 / docs_per_sec
+    } else {
+        return None;
+    };
+
+    Some(IndexEta {
+        done_docs: progress.done_docs,
+        total_docs: progress.total_docs,
+        docs_per_sec,
+        bytes_per_sec,
+        remaining_secs: remaining_secs.round() as u64,
+    })
+}
@@ -0,0 +1,131 @@
+use serde::Deserialize;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+
+// 回答中引用的一个来源
+#[derive(Debug, Clone, Deserialize)]
+pub struct Citation {
+    pub title: String,
+    pub page: Option<u32>,
+    // 文档在数据目录中的存储路径
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ExportFormat {
+    Markdown,
+    Html,
+}
+
+impl ExportFormat {
+    fn parse(format: &str) -> Result<Self, String> {
+        match format {
+            "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            _ => Err(format!("不支持的导出格式: {}", format)),
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// 引用的描述文本，例如 `说明书.pdf（第 3 页）`
+fn describe(citation: &Citation) -> String {
+    match citation.page {
+        Some(page) => format!("{}（第 {} 页）", citation.title, page),
+        None => citation.title.clone(),
+    }
+}
+
+fn render_markdown(answer: &str, citations: &[Citation]) -> String {
+    let mut out = format!("# 回答\n\n{}\n", answer.trim());
+    if !citations.is_empty() {
+        out.push_str("\n## 引用来源\n\n");
+        for (i, citation) in citations.iter().enumerate() {
+            let _ = write!(out, "{}. {}", i + 1, describe(citation));
+            if let Some(path) = &citation.path {
+                let _ = write!(out, " — `{}`", path.replace('`', "'"));
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn render_html(answer: &str, citations: &[Citation]) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n<title>回答</title>\n</head>\n<body>\n<h1>回答</h1>\n",
+    );
+    for paragraph in answer.trim().split("\n\n") {
+        let _ = writeln!(
+            out,
+            "<p>{}</p>",
+            escape_html(paragraph).replace('\n', "<br>")
+        );
+    }
+    if !citations.is_empty() {
+        out.push_str("<h2>引用来源</h2>\n<ol>\n");
+        for citation in citations {
+            let _ = write!(out, "<li>{}", escape_html(&describe(citation)));
+            if let Some(path) = &citation.path {
+                let _ = write!(out, " — <code>{}</code>", escape_html(path));
+            }
+            out.push_str("</li>\n");
+        }
+        out.push_str("</ol>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+// 导出回答及其引用来源，文件路径由用户在保存对话框中选择。
+// 用户取消时返回 None，否则返回实际写入的文件路径
+#[tauri::command]
+pub async fn export_answer(
+    app: AppHandle,
+    answer: String,
+    citations: Vec<Citation>,
+    format: String,
+) -> Result<Option<String>, String> {
+    let format = ExportFormat::parse(&format)?;
+    let extension = format.extension();
+
+    let Some(chosen) = app
+        .dialog()
+        .file()
+        .add_filter(extension, &[extension])
+        .set_file_name(format!("answer.{}", extension))
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+    let mut path: PathBuf = chosen
+        .into_path()
+        .map_err(|e| format!("无效的保存路径: {}", e))?;
+    // 只写入用户选择的那一个文件，并保证扩展名与导出格式一致
+    if path.extension().and_then(|ext| ext.to_str()) != Some(extension) {
+        path.set_extension(extension);
+    }
+
+    let content = match format {
+        ExportFormat::Markdown => render_markdown(&answer, &citations),
+        ExportFormat::Html => render_html(&answer, &citations),
+    };
+    std::fs::write(&path, content).map_err(|e| format!("写入导出文件失败: {}", e))?;
+    log::info!("回答已导出到 {:?}", path);
+    Ok(Some(path.to_string_lossy().to_string()))
+}
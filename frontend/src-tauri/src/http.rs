@@ -0,0 +1,205 @@
+use futures_util::StreamExt;
+use serde_json::Value;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::RwLock;
+use std::time::Duration;
+
+// 后端服务监听的端口（与后端 config.py 中的 PORT 默认值一致）
+pub const BACKEND_PORT: u16 = 12345;
+
+// 后端默认只监听本机回环地址
+pub const DEFAULT_BACKEND_HOST: &str = "127.0.0.1";
+
+// 与后端通信共用的 HTTP 客户端（复用连接池），请求地址使用配置的后端主机
+pub struct BackendClient {
+    http: reqwest::Client,
+    base_url: RwLock<String>,
+}
+
+impl BackendClient {
+    pub fn new(host: &str) -> Self {
+        Self::with_port(host, BACKEND_PORT)
+    }
+
+    // 连接监听在其他端口上的后端（例如自检时启动的临时后端）
+    pub fn with_port(host: &str, port: u16) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: RwLock::new(base_url(host, port)),
+        }
+    }
+
+    // 后端主机配置变更后更新请求地址
+    pub fn set_host(&self, host: &str) {
+        *self.base_url.write().unwrap() = base_url(host, BACKEND_PORT);
+    }
+
+    // 拼接后端接口地址，path 需以 `/` 开头
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url.read().unwrap(), path)
+    }
+
+    pub fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.http.request(method, self.url(path))
+    }
+
+    pub fn get(&self, path: &str) -> reqwest::RequestBuilder {
+        self.http.get(self.url(path))
+    }
+
+    pub fn post(&self, path: &str) -> reqwest::RequestBuilder {
+        self.http.post(self.url(path))
+    }
+}
+
+// 连接失败时最多尝试的次数（含第一次）
+const MAX_ATTEMPTS: u32 = 3;
+// 重试的基础退避时间，每次翻倍并加上随机抖动
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+
+// 退避时间：base * 2^attempt，再加上 [0, base) 的随机抖动，避免多个请求同时重试
+fn retry_delay(attempt: u32) -> Duration {
+    let base = RETRY_BASE_DELAY.as_millis() as u64;
+    let jitter = RandomState::new().build_hasher().finish() % base;
+    Duration::from_millis(base * 2u64.pow(attempt) + jitter)
+}
+
+// 发送请求，连接级错误（例如后端启动期间拒绝连接）时带抖动地重试。
+// 只有 idempotent 为 true 的请求才会重试；已收到的 HTTP 错误状态不重试，
+// 请求体无法复制（流式请求体）时也只发送一次
+pub async fn send(
+    request: reqwest::RequestBuilder,
+    idempotent: bool,
+) -> reqwest::Result<reqwest::Response> {
+    if !idempotent {
+        return request.send().await;
+    }
+    let mut attempt = 0;
+    loop {
+        let Some(retry) = request.try_clone() else {
+            return request.send().await;
+        };
+        match retry.send().await {
+            Err(e) if e.is_connect() && attempt + 1 < MAX_ATTEMPTS => {
+                let delay = retry_delay(attempt);
+                attempt += 1;
+                log::warn!(
+                    "连接后端失败，{} ms 后重试（{}/{}）: {}",
+                    delay.as_millis(),
+                    attempt,
+                    MAX_ATTEMPTS - 1,
+                    e
+                );
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+// 监听所有网卡（0.0.0.0 / ::）时通过本机回环地址访问
+fn base_url(host: &str, port: u16) -> String {
+    let connect_host = match host {
+        "0.0.0.0" => "127.0.0.1",
+        "::" => "[::1]",
+        host if host.contains(':') => return format!("http://[{}]:{}", host, port),
+        host => host,
+    };
+    format!("http://{}:{}", connect_host, port)
+}
+
+// 逐个解析 SSE 响应中的 `data:` 事件并交给回调处理。
+// 回调返回 Ok(true) 表示流已正常结束；流在此之前断开则返回错误
+pub async fn read_sse_events<F>(response: reqwest::Response, mut on_event: F) -> Result<(), String>
+where
+    F: FnMut(Value) -> Result<bool, String>,
+{
+    let mut stream = response.bytes_stream();
+    let mut buffer = String::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("与后端的连接中断: {}", e))?;
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+        // SSE 事件之间以空行分隔
+        while let Some(end) = buffer.find("\n\n") {
+            let raw: String = buffer.drain(..end + 2).collect();
+            for data in raw.lines().filter_map(|line| line.strip_prefix("data:")) {
+                let Ok(event) = serde_json::from_str::<Value>(data.trim()) else {
+                    continue;
+                };
+                if on_event(event)? {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    Err("后端在处理完成前断开了连接".to_string())
+}
+
+// 后端健康检查，超时或返回非 2xx 均视为不健康
+pub async fn check_health(client: &BackendClient) -> bool {
+    client
+        .get("/health")
+        .timeout(Duration::from_secs(3))
+        .send()
+        .await
+        .is_ok_and(|response| response.status().is_success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    // 找一个当前没有监听的本机端口：绑定后立即释放，连接该端口会被拒绝
+    async fn free_port() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap().port()
+    }
+
+    // 在 delay 之后才开始监听 port，对收到的第一个请求返回 200
+    fn serve_later(port: u16, delay: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let listener = TcpListener::bind(("127.0.0.1", port)).await.unwrap();
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = vec![0; 4096];
+            let mut request = Vec::new();
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                let read = stream.read(&mut buffer).await.unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..read]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .await
+                .unwrap();
+        })
+    }
+
+    #[tokio::test]
+    async fn idempotent_request_retries_until_backend_accepts() {
+        let port = free_port().await;
+        // 第一次连接被拒绝，重试等待（至少 RETRY_BASE_DELAY）期间后端开始监听
+        let server = serve_later(port, RETRY_BASE_DELAY / 2);
+        let client = BackendClient::with_port(DEFAULT_BACKEND_HOST, port);
+        let response = send(client.get("/health"), true).await.unwrap();
+        assert!(response.status().is_success());
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn non_idempotent_request_is_sent_once() {
+        let port = free_port().await;
+        let server = serve_later(port, RETRY_BASE_DELAY / 2);
+        let client = BackendClient::with_port(DEFAULT_BACKEND_HOST, port);
+        let error = send(client.post("/api/v1/query"), false).await.unwrap_err();
+        assert!(error.is_connect());
+        server.abort();
+    }
+}
@@ -0,0 +1,601 @@
+use futures_util::future::Abortable;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::backend::BackendProcess;
+use crate::eta::IndexThroughput;
+use crate::http::{self, check_health, BackendClient};
+use crate::quota::{self, VaultQuota};
+use crate::recent::{RecentAction, RecentDocuments};
+use crate::requests::{PendingRequests, RequestLimiter};
+use crate::watchdog;
+
+// 后端允许的单个文件大小上限（与后端 MAX_UPLOAD_SIZE 默认值一致）
+const MAX_IMPORT_FILE_BYTES: u64 = 10 * 1024 * 1024;
+// 未指定扩展名时导入后端支持的全部类型
+const DEFAULT_EXTENSIONS: [&str; 3] = ["pdf", "txt", "docx"];
+// 明显无用、直接跳过的目录和文件
+const JUNK_NAMES: [&str; 6] = [
+    "node_modules",
+    "__pycache__",
+    "$RECYCLE.BIN",
+    "System Volume Information",
+    "Thumbs.db",
+    "desktop.ini",
+];
+
+// 数据目录中保存待导入队列的文件，暂停跨越应用重启时据此继续
+const QUEUE_FILE: &str = "ingest-queue.json";
+// 继续导入前等待后端就绪的最长时间（暂停期间后端可能已重启）
+const BACKEND_READY_TIMEOUT: Duration = Duration::from_secs(60);
+const BACKEND_POLL_INTERVAL: Duration = Duration::from_secs(1);
+// 导入结束后检查后端是否处理完文档的间隔
+const INDEXING_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// 跳过文件的原因
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    UnsupportedExtension,
+    TooLarge,
+    Duplicate,
+    Unreadable,
+    UploadFailed,
+    VaultFull,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkippedFile {
+    pub path: String,
+    pub reason: SkipReason,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ImportSummary {
+    pub imported: usize,
+    pub skipped: Vec<SkippedFile>,
+    // 导入被暂停时为 true，remaining 为队列中尚未导入的文件数
+    pub paused: bool,
+    pub remaining: usize,
+}
+
+impl ImportSummary {
+    fn skip(&mut self, path: &Path, reason: SkipReason, detail: Option<String>) {
+        self.skipped.push(SkippedFile {
+            path: path.to_string_lossy().to_string(),
+            reason,
+            detail,
+        });
+    }
+}
+
+fn mime_type(extension: &str) -> &'static str {
+    match extension {
+        "pdf" => "application/pdf",
+        "txt" => "text/plain",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        _ => "application/octet-stream",
+    }
+}
+
+fn extension_of(path: &Path) -> String {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+// 隐藏文件、Office 临时文件和常见的无用目录
+fn is_junk(name: &str) -> bool {
+    name.starts_with('.') || name.starts_with("~$") || JUNK_NAMES.contains(&name)
+}
+
+// 上传一个文档到后端，后端会把文件保存到文档库并在后台建立索引
+pub async fn upload_document(
+    client: &BackendClient,
+    file_name: &str,
+    bytes: Vec<u8>,
+) -> Result<Value, String> {
+    let part = reqwest::multipart::Part::bytes(bytes)
+        .file_name(file_name.to_string())
+        .mime_str(mime_type(&extension_of(Path::new(file_name))))
+        .map_err(|e| e.to_string())?;
+    let form = reqwest::multipart::Form::new().part("file", part);
+    let response = http::send(
+        client.post("/api/v1/documents/upload").multipart(form),
+        false,
+    )
+    .await
+    .map_err(|e| format!("连接后端失败: {}", e))?;
+    let status = response.status();
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("解析后端响应失败: {}", e))?;
+    if !status.is_success() {
+        return Err(format!("后端返回错误状态 {}: {}", status, body["detail"]));
+    }
+    Ok(body)
+}
+
+// 遍历目录收集候选文件（跳过隐藏文件和无用目录）
+fn discover(dir: &Path, recursive: bool, files: &mut Vec<PathBuf>, app: &AppHandle) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        log::warn!("无法读取目录 {:?}", dir);
+        return;
+    };
+    for entry in entries.flatten() {
+        if is_junk(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        // 不跟随符号链接，避免目录循环
+        if file_type.is_dir() && recursive {
+            discover(&entry.path(), recursive, files, app);
+        } else if file_type.is_file() {
+            files.push(entry.path());
+            let _ = app.emit(
+                "import-progress",
+                json!({ "phase": "discovered", "path": entry.path(), "count": files.len() }),
+            );
+        }
+    }
+}
+
+// 导入去重：跳过文档库中已有的（文件名 + 大小相同）和本次导入中内容重复的文件
+struct Dedup {
+    known: HashSet<(String, u64)>,
+    contents: HashSet<u64>,
+}
+
+impl Dedup {
+    fn is_new(&mut self, name: &str, size: u64, bytes: &[u8]) -> bool {
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        self.contents.insert(hasher.finish()) && self.known.insert((name.to_string(), size))
+    }
+}
+
+// 队列中的一个待导入文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct QueuedFile {
+    path: PathBuf,
+    size: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct QueueContents {
+    paused: bool,
+    files: VecDeque<QueuedFile>,
+    // 每次清空队列加 1，导入任务据此判断上传失败的文件是否还应放回队列
+    #[serde(skip)]
+    generation: u64,
+}
+
+// 导入队列：逐个取出文件上传，暂停时停止取新文件（正在上传的文件会完成）。
+// 队列和暂停状态保存在磁盘上，应用重启后可以继续
+pub struct IngestQueue {
+    path: PathBuf,
+    contents: Mutex<QueueContents>,
+    // 同一时间只有一个导入任务在处理队列
+    worker: tokio::sync::Mutex<()>,
+}
+
+// 导入队列的当前状态
+#[derive(Debug, Serialize)]
+pub struct IngestionStatus {
+    pub paused: bool,
+    pub remaining: usize,
+}
+
+impl IngestQueue {
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(QUEUE_FILE);
+        let mut contents: QueueContents = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        // 上次退出时还有未导入的文件：保持暂停，等待用户继续
+        if !contents.files.is_empty() {
+            log::info!("导入队列中还有 {} 个文件待导入", contents.files.len());
+            contents.paused = true;
+        }
+        Self {
+            path,
+            contents: Mutex::new(contents),
+            worker: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    fn save(&self, contents: &QueueContents) {
+        if contents.files.is_empty() && !contents.paused {
+            let _ = std::fs::remove_file(&self.path);
+            return;
+        }
+        let result = serde_json::to_string(contents)
+            .map_err(|e| e.to_string())
+            .and_then(|text| std::fs::write(&self.path, text).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            log::warn!("保存导入队列失败: {}", e);
+        }
+    }
+
+    fn status(&self) -> IngestionStatus {
+        let contents = self.contents.lock().unwrap();
+        IngestionStatus {
+            paused: contents.paused,
+            remaining: contents.files.len(),
+        }
+    }
+
+    // 是否有导入任务正在处理队列
+    pub fn is_importing(&self) -> bool {
+        self.worker.try_lock().is_err()
+    }
+
+    fn set_paused(&self, paused: bool) -> IngestionStatus {
+        let mut contents = self.contents.lock().unwrap();
+        contents.paused = paused;
+        self.save(&contents);
+        IngestionStatus {
+            paused,
+            remaining: contents.files.len(),
+        }
+    }
+
+    fn extend(&self, files: Vec<(PathBuf, u64)>) {
+        let mut contents = self.contents.lock().unwrap();
+        contents.files.extend(
+            files
+                .into_iter()
+                .map(|(path, size)| QueuedFile { path, size }),
+        );
+        self.save(&contents);
+    }
+
+    // 取出下一个文件；暂停或队列为空时返回 None
+    fn next(&self) -> Option<QueuedFile> {
+        let mut contents = self.contents.lock().unwrap();
+        if contents.paused {
+            return None;
+        }
+        let file = contents.files.pop_front();
+        self.save(&contents);
+        file
+    }
+
+    // 清空队列并保存（同时取消暂停状态），返回清除的文件数。
+    // 正在上传的文件不受影响，上传失败时也不会再放回队列
+    pub fn clear(&self) -> usize {
+        let mut contents = self.contents.lock().unwrap();
+        let cleared = contents.files.len();
+        contents.files.clear();
+        contents.paused = false;
+        contents.generation += 1;
+        self.save(&contents);
+        cleared
+    }
+
+    fn generation(&self) -> u64 {
+        self.contents.lock().unwrap().generation
+    }
+
+    fn totals(&self) -> (u64, u64) {
+        let contents = self.contents.lock().unwrap();
+        (
+            contents.files.len() as u64,
+            contents.files.iter().map(|file| file.size).sum(),
+        )
+    }
+}
+
+// 已在文档库中的文档（文件名 + 大小）
+async fn existing_documents(client: &BackendClient) -> HashSet<(String, u64)> {
+    let Ok(response) = http::send(client.get("/api/v1/documents?limit=10000"), true).await else {
+        return HashSet::new();
+    };
+    let documents: Vec<Value> = response.json().await.unwrap_or_default();
+    documents
+        .iter()
+        .filter_map(|doc| {
+            Some((
+                doc["filename"].as_str()?.to_string(),
+                doc["file_size"].as_u64()?,
+            ))
+        })
+        .collect()
+}
+
+// 读取、去重并上传一个文件，结果记入 summary
+async fn import_file(
+    app: &AppHandle,
+    client: &BackendClient,
+    limiter: &RequestLimiter,
+    file: &Path,
+    size: u64,
+    dedup: &mut Dedup,
+    summary: &mut ImportSummary,
+) -> Result<(), String> {
+    let name = file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let bytes = match std::fs::read(file) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            summary.skip(file, SkipReason::Unreadable, Some(e.to_string()));
+            return Ok(());
+        }
+    };
+    if !dedup.is_new(&name, size, &bytes) {
+        summary.skip(file, SkipReason::Duplicate, None);
+        return Ok(());
+    }
+
+    if let Err(e) = quota::check(app, size).await {
+        summary.skip(file, SkipReason::VaultFull, Some(e));
+        return Ok(());
+    }
+
+    // 登记上传请求后再排队等待名额，排队中的上传同样可以被 cancel_all 取消
+    let pending = app.state::<PendingRequests>();
+    let request_id = pending.generate_id();
+    let registration = pending.register(&request_id)?;
+    let upload = async {
+        let _permit = limiter.acquire().await?;
+        Ok::<_, String>(upload_document(client, &name, bytes).await)
+    };
+    let result = Abortable::new(upload, registration).await;
+    pending.finish(&request_id);
+    let uploaded = result.unwrap_or_else(|_| Err(format!("导入 {:?} 已取消", file)))?;
+    match uploaded {
+        Ok(body) => {
+            summary.imported += 1;
+            app.state::<VaultQuota>().add(size);
+            let id = match &body["id"] {
+                Value::Null => file.to_string_lossy().to_string(),
+                Value::String(id) => id.clone(),
+                id => id.to_string(),
+            };
+            app.state::<RecentDocuments>().record(
+                id,
+                name,
+                Some(file.to_path_buf()),
+                RecentAction::Ingested,
+            );
+            let _ = app.emit(
+                "import-progress",
+                json!({ "phase": "queued", "path": file, "count": summary.imported }),
+            );
+        }
+        // 后端的上传接口同样检查文档库上限（例如其他上传在缓存期内占用了空间）
+        Err(e) if e.contains(quota::VAULT_FULL) => {
+            summary.skip(file, SkipReason::VaultFull, Some(e));
+        }
+        Err(e) => {
+            log::warn!("导入 {:?} 失败: {}", file, e);
+            summary.skip(file, SkipReason::UploadFailed, Some(e));
+        }
+    }
+    Ok(())
+}
+
+// 从文件夹批量导入文档：按扩展名和大小筛选，跳过文档库中已有的和本次重复的文件，
+// 逐个上传给后端建立索引。发现和入队文件时发送 import-progress 事件
+#[tauri::command]
+pub async fn import_folder(
+    app: AppHandle,
+    limiter: State<'_, RequestLimiter>,
+    path: String,
+    recursive: bool,
+    extensions: Vec<String>,
+) -> Result<ImportSummary, String> {
+    let root = PathBuf::from(&path);
+    if !root.is_dir() {
+        return Err(format!("不是有效的文件夹: {}", path));
+    }
+    let extensions: Vec<String> = if extensions.is_empty() {
+        DEFAULT_EXTENSIONS
+            .iter()
+            .map(|ext| ext.to_string())
+            .collect()
+    } else {
+        extensions
+            .iter()
+            .map(|ext| ext.trim_start_matches('.').to_lowercase())
+            .collect()
+    };
+
+    let discovering = app.clone();
+    let files = tauri::async_runtime::spawn_blocking(move || {
+        let mut files = Vec::new();
+        discover(&root, recursive, &mut files, &discovering);
+        files
+    })
+    .await
+    .map_err(|e| format!("遍历文件夹失败: {}", e))?;
+    log::info!("在 {} 中发现 {} 个文件", path, files.len());
+
+    let mut summary = ImportSummary::default();
+
+    // 先按扩展名和大小筛选，得到待导入文件的总量用于估算剩余时间
+    let mut candidates = Vec::new();
+    for file in files {
+        if !extensions.contains(&extension_of(&file)) {
+            summary.skip(&file, SkipReason::UnsupportedExtension, None);
+            continue;
+        }
+        match std::fs::metadata(&file) {
+            Ok(meta) if meta.len() > MAX_IMPORT_FILE_BYTES => {
+                let detail = format!("{} 字节", meta.len());
+                summary.skip(&file, SkipReason::TooLarge, Some(detail));
+            }
+            Ok(meta) => candidates.push((file, meta.len())),
+            Err(e) => summary.skip(&file, SkipReason::Unreadable, Some(e.to_string())),
+        }
+    }
+
+    // 整批文件放入文档库会超出大小上限时直接拒绝
+    quota::check(&app, candidates.iter().map(|(_, size)| size).sum()).await?;
+
+    // 放入导入队列；已有导入任务时等它结束后一并处理
+    let queue = app.state::<IngestQueue>();
+    queue.extend(candidates);
+    let _worker = queue.worker.lock().await;
+    drain_queue(&app, &limiter, &mut summary).await?;
+    log::info!(
+        "文件夹导入结束：导入 {} 个，跳过 {} 个，剩余 {} 个",
+        summary.imported,
+        summary.skipped.len(),
+        summary.remaining
+    );
+    Ok(summary)
+}
+
+// 逐个导入队列中的文件，直到队列为空或被暂停
+async fn drain_queue(
+    app: &AppHandle,
+    limiter: &RequestLimiter,
+    summary: &mut ImportSummary,
+) -> Result<(), String> {
+    let queue = app.state::<IngestQueue>();
+    let client = app.state::<BackendClient>();
+    let mut dedup = Dedup {
+        known: existing_documents(&client).await,
+        contents: HashSet::new(),
+    };
+    let throughput = app.state::<IndexThroughput>();
+    let (total_docs, total_bytes) = queue.totals();
+    throughput.start(total_docs, total_bytes);
+
+    let (mut done_docs, mut done_bytes) = (0, 0);
+    while let Some(file) = queue.next() {
+        let generation = queue.generation();
+        let result = import_file(
+            app, &client, limiter, &file.path, file.size, &mut dedup, summary,
+        )
+        .await;
+        if let Err(e) = result {
+            // 没能上传的文件放回队首，继续导入时重试；期间队列已被清空时不放回
+            let mut contents = queue.contents.lock().unwrap();
+            if contents.generation == generation {
+                contents.files.push_front(file);
+                queue.save(&contents);
+            }
+            throughput.finish();
+            return Err(e);
+        }
+        done_docs += 1;
+        done_bytes += file.size;
+        throughput.record(done_docs, done_bytes);
+    }
+    throughput.finish();
+
+    let status = queue.status();
+    summary.paused = status.paused;
+    summary.remaining = status.remaining;
+    if !status.paused && summary.imported > 0 {
+        notify_indexing_done(app, summary.imported);
+    }
+    Ok(())
+}
+
+// 后端在后台处理上传的文档：等到没有等待或正在处理的文档后发送 indexing-done 事件
+fn notify_indexing_done(app: &AppHandle, imported: usize) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(INDEXING_POLL_INTERVAL).await;
+            if app.state::<BackendProcess>().shutting_down() {
+                return;
+            }
+            if !watchdog::is_indexing(&app.state::<BackendClient>()).await {
+                break;
+            }
+        }
+        log::info!("导入的 {} 个文档已处理完成", imported);
+        let _ = app.emit("indexing-done", json!({ "imported": imported }));
+    });
+}
+
+// 暂停导入：正在上传的文件完成后不再取新文件，队列保存在磁盘上
+#[tauri::command]
+pub fn pause_ingestion(app: AppHandle, queue: State<'_, IngestQueue>) -> IngestionStatus {
+    let status = queue.set_paused(true);
+    log::info!("导入已暂停，剩余 {} 个文件", status.remaining);
+    let _ = app.emit("ingestion-paused", &status);
+    status
+}
+
+// 继续导入暂停前（或上次运行时）剩余的文件。已有导入任务在运行时它会自行继续，返回 None
+#[tauri::command]
+pub async fn resume_ingestion(
+    app: AppHandle,
+    limiter: State<'_, RequestLimiter>,
+) -> Result<Option<ImportSummary>, String> {
+    let queue = app.state::<IngestQueue>();
+    let status = queue.set_paused(false);
+    let _ = app.emit("ingestion-resumed", &status);
+    let Ok(_worker) = queue.worker.try_lock() else {
+        return Ok(None);
+    };
+    if status.remaining == 0 {
+        return Ok(Some(ImportSummary::default()));
+    }
+    log::info!("继续导入剩余的 {} 个文件", status.remaining);
+
+    // 暂停期间后端可能已重启，等它就绪后再上传
+    let client = app.state::<BackendClient>();
+    let started = Instant::now();
+    while !check_health(&client).await {
+        if started.elapsed() >= BACKEND_READY_TIMEOUT {
+            queue.set_paused(true);
+            return Err("后端未就绪，导入仍处于暂停状态".to_string());
+        }
+        tokio::time::sleep(BACKEND_POLL_INTERVAL).await;
+    }
+
+    let mut summary = ImportSummary::default();
+    drain_queue(&app, &limiter, &mut summary).await?;
+    Ok(Some(summary))
+}
+
+// 导入队列的暂停状态和剩余文件数（应用重启后用于提示继续导入）
+#[tauri::command]
+pub fn get_ingestion_status(queue: State<'_, IngestQueue>) -> IngestionStatus {
+    queue.status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clear_empties_and_persists_queue() {
+        let dir = std::env::temp_dir().join(format!("document-qa-queue-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let queue = IngestQueue::load(&dir);
+        queue.extend(vec![(dir.join("a.pdf"), 10), (dir.join("b.pdf"), 20)]);
+        queue.set_paused(true);
+        assert!(dir.join(QUEUE_FILE).exists());
+
+        assert_eq!(queue.clear(), 2);
+        let status = queue.status();
+        assert_eq!((status.paused, status.remaining), (false, 0));
+        // 清空后重新加载也没有待导入的文件
+        let reloaded = IngestQueue::load(&dir).status();
+        assert_eq!((reloaded.paused, reloaded.remaining), (false, 0));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
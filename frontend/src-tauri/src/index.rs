@@ -0,0 +1,434 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Listener, Manager, State};
+
+use crate::backend::{now_millis, BackendProcess};
+use crate::config::ConfigState;
+use crate::credentials;
+use crate::eta::IndexThroughput;
+use crate::http::{self, read_sse_events, BackendClient};
+use crate::paths::{dir_size, AppPaths};
+
+// 索引/文档库维护任务的状态，同一时间只允许一个维护任务运行
+pub struct IndexMaintenance(Mutex<Option<&'static str>>);
+
+impl IndexMaintenance {
+    pub fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+
+    // 开始一个维护任务，已有任务在运行时返回错误
+    pub fn begin(&self, operation: &'static str) -> Result<MaintenanceGuard<'_>, String> {
+        let mut current = self.0.lock().unwrap();
+        if let Some(running) = *current {
+            return Err(format!("正在执行{}任务，请稍后再试", running));
+        }
+        *current = Some(operation);
+        Ok(MaintenanceGuard(self))
+    }
+}
+
+// 任务结束（包括出错提前返回）时自动清除运行状态
+pub struct MaintenanceGuard<'a>(&'a IndexMaintenance);
+
+impl Drop for MaintenanceGuard<'_> {
+    fn drop(&mut self) {
+        *self.0 .0.lock().unwrap() = None;
+    }
+}
+
+// 把后端进度事件中的 done/total（可选 bytes_done/bytes_total）计入吞吐量统计
+fn record_progress(app: &AppHandle, event: &Value) {
+    let (Some(done), Some(total)) = (event["done"].as_u64(), event["total"].as_u64()) else {
+        return;
+    };
+    let throughput = app.state::<IndexThroughput>();
+    if done == 0 || !throughput.is_active() {
+        throughput.start(total, event["bytes_total"].as_u64().unwrap_or(0));
+    }
+    throughput.record(done, event["bytes_done"].as_u64().unwrap_or(0));
+}
+
+// 修复结果；后端无法修复时建议执行 reindex_all 全量重建
+#[derive(Debug, Serialize)]
+pub struct RepairOutcome {
+    pub repaired: bool,
+    pub report: Value,
+    pub suggest_reindex: bool,
+}
+
+// 检查结果；后端没有检查接口时 supported 为 false，与修复一样建议执行 reindex_all 全量重建
+#[derive(Debug, Serialize)]
+pub struct VerifyOutcome {
+    pub supported: bool,
+    pub report: Value,
+    pub suggest_reindex: bool,
+}
+
+// 让后端检查向量索引的完整性并返回检查报告
+#[tauri::command]
+pub async fn verify_index(client: State<'_, BackendClient>) -> Result<VerifyOutcome, String> {
+    let response = http::send(client.get("/api/v1/index/verify"), true)
+        .await
+        .map_err(|e| format!("连接后端失败: {}", e))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::NOT_IMPLEMENTED {
+        log::warn!("后端不支持检查索引，建议执行全量重建");
+        return Ok(VerifyOutcome {
+            supported: false,
+            report: Value::Null,
+            suggest_reindex: true,
+        });
+    }
+    if !status.is_success() {
+        return Err(format!("索引检查失败: {}", status));
+    }
+    let report = response
+        .json()
+        .await
+        .map_err(|e| format!("解析检查报告失败: {}", e))?;
+    Ok(VerifyOutcome {
+        supported: true,
+        report,
+        suggest_reindex: false,
+    })
+}
+
+// 让后端修复（重建）损坏的索引分段，修复过程中发送 index-repair-progress 事件
+#[tauri::command]
+pub async fn repair_index(
+    app: AppHandle,
+    client: State<'_, BackendClient>,
+    maintenance: State<'_, IndexMaintenance>,
+) -> Result<RepairOutcome, String> {
+    let _guard = maintenance.begin("修复")?;
+    log::info!("开始修复索引");
+
+    let response = http::send(client.post("/api/v1/index/repair"), false)
+        .await
+        .map_err(|e| format!("连接后端失败: {}", e))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::NOT_IMPLEMENTED {
+        log::warn!("后端不支持局部修复索引，建议执行全量重建");
+        return Ok(RepairOutcome {
+            repaired: false,
+            report: Value::Null,
+            suggest_reindex: true,
+        });
+    }
+    if !status.is_success() {
+        return Err(format!("索引修复失败: {}", status));
+    }
+
+    let mut outcome = RepairOutcome {
+        repaired: false,
+        report: Value::Null,
+        suggest_reindex: false,
+    };
+    read_sse_events(response, |event| match event["type"].as_str() {
+        Some("progress") => {
+            record_progress(&app, &event);
+            let _ = app.emit("index-repair-progress", &event);
+            Ok(false)
+        }
+        Some("done") => {
+            outcome.repaired = event["repaired"].as_bool().unwrap_or(true);
+            outcome.suggest_reindex = !outcome.repaired;
+            outcome.report = event;
+            Ok(true)
+        }
+        Some("error") => Err(event["message"]
+            .as_str()
+            .unwrap_or("后端修复索引失败")
+            .to_string()),
+        _ => Ok(false),
+    })
+    .await?;
+
+    app.state::<IndexThroughput>().finish();
+    log::info!("索引修复结束，是否修复成功: {}", outcome.repaired);
+    Ok(outcome)
+}
+
+// 压缩结果；后端没有压缩接口时建议执行全量重建
+#[derive(Debug, Serialize)]
+pub struct CompactOutcome {
+    pub compacted: bool,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub bytes_reclaimed: u64,
+    pub suggest_reindex: bool,
+}
+
+// 让后端压缩（vacuum）索引存储，回收删除文档后留下的空间。
+// 压缩过程中发送 index-compact-progress 事件，占用空间以索引目录的实际大小为准
+#[tauri::command]
+pub async fn compact_index(
+    app: AppHandle,
+    client: State<'_, BackendClient>,
+    maintenance: State<'_, IndexMaintenance>,
+    paths: State<'_, AppPaths>,
+) -> Result<CompactOutcome, String> {
+    let _guard = maintenance.begin("压缩")?;
+    let index_dir = paths.index_dir();
+    let bytes_before = dir_size(&index_dir);
+    log::info!("开始压缩索引，当前大小 {} 字节", bytes_before);
+
+    let response = http::send(client.post("/api/v1/index/compact"), false)
+        .await
+        .map_err(|e| format!("连接后端失败: {}", e))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::NOT_IMPLEMENTED {
+        log::warn!("后端不支持压缩索引，建议执行全量重建");
+        return Ok(CompactOutcome {
+            compacted: false,
+            bytes_before,
+            bytes_after: bytes_before,
+            bytes_reclaimed: 0,
+            suggest_reindex: true,
+        });
+    }
+    if !status.is_success() {
+        return Err(format!("索引压缩失败: {}", status));
+    }
+
+    read_sse_events(response, |event| match event["type"].as_str() {
+        Some("progress") => {
+            let _ = app.emit("index-compact-progress", &event);
+            Ok(false)
+        }
+        Some("done") => Ok(true),
+        Some("error") => Err(event["message"]
+            .as_str()
+            .unwrap_or("后端压缩索引失败")
+            .to_string()),
+        _ => Ok(false),
+    })
+    .await?;
+
+    let bytes_after = dir_size(&index_dir);
+    let bytes_reclaimed = bytes_before.saturating_sub(bytes_after);
+    log::info!("索引压缩完成，回收 {} 字节", bytes_reclaimed);
+    Ok(CompactOutcome {
+        compacted: true,
+        bytes_before,
+        bytes_after,
+        bytes_reclaimed,
+        suggest_reindex: false,
+    })
+}
+
+// 后端索引文档期间在文档库中创建的锁文件名，内容为后端进程 pid；
+// 后端崩溃时锁文件会残留下来
+const INDEX_LOCK_FILE: &str = "index.lock";
+
+#[derive(Debug, Serialize)]
+pub struct IndexLock {
+    pub exists: bool,
+    pub path: String,
+    // 锁文件的存在时长（秒）
+    pub age_secs: Option<u64>,
+    pub pid: Option<u32>,
+    // 持有锁的进程是否仍在运行
+    pub holder_alive: bool,
+    // 锁存在但没有存活的进程持有，可以安全清除
+    pub stale: bool,
+}
+
+fn inspect_lock(app: &AppHandle) -> IndexLock {
+    let path = app.state::<AppPaths>().vault_dir().join(INDEX_LOCK_FILE);
+    let path_str = path.to_string_lossy().to_string();
+    let Ok(meta) = std::fs::metadata(&path) else {
+        return IndexLock {
+            exists: false,
+            path: path_str,
+            age_secs: None,
+            pid: None,
+            holder_alive: false,
+            stale: false,
+        };
+    };
+    let age_secs = meta
+        .modified()
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .map(|age| age.as_secs());
+    let pid = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|text| text.trim().parse::<u32>().ok());
+
+    // 锁文件没有记录 pid 时，只要我们的后端还在运行就认为锁可能被它持有
+    let holder_alive = match pid {
+        Some(pid) => {
+            let pid = sysinfo::Pid::from_u32(pid);
+            let mut system = sysinfo::System::new();
+            system.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[pid]), true);
+            system.process(pid).is_some()
+        }
+        None => app.state::<BackendProcess>().pid().is_some(),
+    };
+    IndexLock {
+        exists: true,
+        path: path_str,
+        age_secs,
+        pid,
+        holder_alive,
+        stale: !holder_alive,
+    }
+}
+
+// 检查文档库中是否有崩溃的索引任务留下的锁文件
+#[tauri::command]
+pub fn check_index_lock(app: AppHandle) -> IndexLock {
+    inspect_lock(&app)
+}
+
+// 清除残留的索引锁；持有锁的进程仍在运行时拒绝清除。返回是否删除了锁文件
+#[tauri::command]
+pub fn clear_index_lock(app: AppHandle) -> Result<bool, String> {
+    let lock = inspect_lock(&app);
+    if !lock.exists {
+        return Ok(false);
+    }
+    if lock.holder_alive {
+        return Err(match lock.pid {
+            Some(pid) => format!("索引锁正被运行中的进程 (pid {}) 持有", pid),
+            None => "索引锁可能正被运行中的后端持有，请先停止后端".to_string(),
+        });
+    }
+    std::fs::remove_file(&lock.path).map_err(|e| format!("删除索引锁失败: {}", e))?;
+    log::info!("已清除残留的索引锁 {}", lock.path);
+    let _ = app.emit("index-lock-cleared", &lock);
+    Ok(true)
+}
+
+// 文档库中记录建立索引时所用嵌入模型的文件
+const INDEX_META_FILE: &str = "index-meta.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexMeta {
+    embedding_model: String,
+    // Unix 时间戳（毫秒）
+    updated_at: u64,
+}
+
+// 当前配置下后端实际使用的嵌入模型：使用远程接口且配置了密钥时为 openai:<模型名>，否则为本地模型
+fn current_embedding_model(app: &AppHandle) -> String {
+    let provider = app.state::<ConfigState>().get().embedding_provider;
+    if provider.requires_api_key() && credentials::api_key_configured(app) {
+        format!(
+            "{}:{}",
+            provider.as_str(),
+            credentials::embedding_model(app)
+        )
+    } else {
+        "local".to_string()
+    }
+}
+
+fn write_index_meta(app: &AppHandle, embedding_model: String) {
+    let path = app.state::<AppPaths>().vault_dir().join(INDEX_META_FILE);
+    let meta = IndexMeta {
+        embedding_model,
+        updated_at: now_millis(),
+    };
+    let result = serde_json::to_string_pretty(&meta)
+        .map_err(|e| e.to_string())
+        .and_then(|text| std::fs::write(&path, text).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        log::warn!("写入索引元数据 {:?} 失败: {}", path, e);
+    }
+}
+
+// 全量重建索引后记录当前使用的嵌入模型
+pub fn record_index_model(app: &AppHandle) {
+    write_index_meta(app, current_embedding_model(app));
+}
+
+// 没有元数据时无从得知索引所用的嵌入模型
+const UNKNOWN_INDEX_MODEL: &str = "unknown";
+
+// 文档库中还没有上传过文档时索引为空，可以直接按当前模型建立
+fn index_is_empty(vault_dir: &Path) -> bool {
+    dir_size(&vault_dir.join("uploads")) == 0
+}
+
+// 比较建立索引时的嵌入模型和当前配置，返回 (是否一致, 索引所用模型, 当前模型)。
+// 索引为空时按当前模型记录元数据；旧版本建立的非空索引没有元数据，
+// 无从判断所用模型，报告为 unknown 并建议重建索引
+fn compare_index_model(app: &AppHandle) -> (bool, String, String) {
+    let current = current_embedding_model(app);
+    let vault_dir = app.state::<AppPaths>().vault_dir();
+    let path = vault_dir.join(INDEX_META_FILE);
+    let indexed = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|text| serde_json::from_str::<IndexMeta>(&text).ok());
+    match indexed {
+        Some(meta) => (
+            meta.embedding_model == current,
+            meta.embedding_model,
+            current,
+        ),
+        None if index_is_empty(&vault_dir) => {
+            write_index_meta(app, current.clone());
+            (true, current.clone(), current)
+        }
+        None => (false, UNKNOWN_INDEX_MODEL.to_string(), current),
+    }
+}
+
+// 当前文档库的索引是否是用当前配置的嵌入模型建立的
+#[tauri::command]
+pub fn index_model_matches(app: AppHandle) -> bool {
+    compare_index_model(&app).0
+}
+
+fn check_index_model(app: &AppHandle) {
+    let (matches, indexed, current) = compare_index_model(app);
+    if !matches {
+        log::warn!(
+            "索引由嵌入模型 {} 建立，与当前模型 {} 不一致，需要重建索引",
+            indexed,
+            current
+        );
+        let _ = app.emit(
+            "index-stale",
+            serde_json::json!({ "indexed_model": indexed, "current_model": current }),
+        );
+    }
+}
+
+// 后端每次就绪时检查索引与嵌入模型是否一致，不一致时发送 index-stale 事件
+pub fn watch_index_model(app: &AppHandle) {
+    if cfg!(debug_assertions) {
+        // 开发模式下后端由开发者自行启动，不会有 backend-ready 事件
+        check_index_model(app);
+    } else {
+        let handle = app.clone();
+        app.listen("backend-ready", move |_| check_index_model(&handle));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_without_uploads_is_empty() {
+        let vault_dir =
+            std::env::temp_dir().join(format!("document-qa-index-empty-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&vault_dir);
+        std::fs::create_dir_all(vault_dir.join("chroma")).unwrap();
+        std::fs::write(vault_dir.join("chroma").join("chroma.sqlite3"), "db").unwrap();
+        assert!(index_is_empty(&vault_dir));
+
+        std::fs::create_dir_all(vault_dir.join("uploads")).unwrap();
+        std::fs::write(vault_dir.join("uploads").join("a.pdf"), "doc").unwrap();
+        assert!(!index_is_empty(&vault_dir));
+        let _ = std::fs::remove_dir_all(&vault_dir);
+    }
+}
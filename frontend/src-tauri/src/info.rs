@@ -0,0 +1,40 @@
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, State};
+
+use crate::config::ConfigState;
+use crate::logs::BackendLogs;
+use crate::paths::AppPaths;
+use crate::safemode::SafeMode;
+use crate::sidecar::{self, BackendSource};
+
+// 应用基本信息，用于“关于”页面和技术支持
+#[derive(Debug, Serialize)]
+pub struct AppInfo {
+    pub version: String,
+    pub data_dir: PathBuf,
+    pub log_dir: PathBuf,
+    pub vault_dir: PathBuf,
+    pub privacy_mode: bool,
+    // 后端以安全模式运行（不自动建立索引）
+    pub safe_mode: bool,
+    // 后端可执行文件来源：打包的或自定义的
+    pub backend_source: BackendSource,
+}
+
+#[tauri::command]
+pub fn app_info(
+    app: AppHandle,
+    paths: State<'_, AppPaths>,
+    logs: State<'_, BackendLogs>,
+) -> AppInfo {
+    AppInfo {
+        version: app.package_info().version.to_string(),
+        data_dir: paths.data_dir.clone(),
+        log_dir: paths.log_dir.clone(),
+        vault_dir: paths.vault_dir(),
+        privacy_mode: logs.privacy_mode(),
+        safe_mode: app.state::<SafeMode>().is_active(),
+        backend_source: sidecar::source(&app.state::<ConfigState>().get()),
+    }
+}
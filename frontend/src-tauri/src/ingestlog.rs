@@ -0,0 +1,99 @@
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::backend::now_millis;
+
+// 后端处理文档时输出的日志前缀：[doc:ID] 消息
+const TAG_PREFIX: &str = "[doc:";
+// 每个文档最多保留的日志行数
+const MAX_LINES_PER_DOCUMENT: usize = 200;
+// 最多保留日志的文档数，超出时丢弃最早开始记录的文档
+const MAX_DOCUMENTS: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IngestLogLine {
+    // Unix 时间戳（毫秒）
+    pub timestamp: u64,
+    pub level: &'static str,
+    pub message: String,
+}
+
+#[derive(Default)]
+struct Inner {
+    logs: HashMap<String, VecDeque<IngestLogLine>>,
+    // 按开始记录的先后排列的文档 ID，用于淘汰最早的文档
+    order: VecDeque<String>,
+}
+
+// 按文档归类的最近处理日志，方便在批量导入中找到单个文档失败的原因
+#[derive(Default)]
+pub struct DocumentIngestLogs(Mutex<Inner>);
+
+// 解析 [doc:ID] 前缀，返回文档 ID 和去掉前缀后的消息
+fn parse_tag(message: &str) -> Option<(&str, &str)> {
+    let start = message.find(TAG_PREFIX)?;
+    let rest = &message[start + TAG_PREFIX.len()..];
+    let end = rest.find(']')?;
+    let id = &rest[..end];
+    if id.is_empty() {
+        return None;
+    }
+    Some((id, rest[end + 1..].trim_start()))
+}
+
+impl DocumentIngestLogs {
+    fn push(&self, id: &str, line: IngestLogLine) {
+        let mut inner = self.0.lock().unwrap();
+        if !inner.logs.contains_key(id) {
+            if inner.order.len() >= MAX_DOCUMENTS {
+                if let Some(oldest) = inner.order.pop_front() {
+                    inner.logs.remove(&oldest);
+                }
+            }
+            inner.order.push_back(id.to_string());
+        }
+        let lines = inner.logs.entry(id.to_string()).or_default();
+        if lines.len() >= MAX_LINES_PER_DOCUMENT {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    fn get(&self, id: &str) -> Vec<IngestLogLine> {
+        self.0
+            .lock()
+            .unwrap()
+            .logs
+            .get(id)
+            .map(|lines| lines.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+// 后端输出行带有文档标记时记入对应文档的日志，并推送 document-ingest-log 事件
+pub fn capture(app: &AppHandle, logs: &DocumentIngestLogs, level: &'static str, message: &str) {
+    let Some((id, text)) = parse_tag(message) else {
+        return;
+    };
+    let line = IngestLogLine {
+        timestamp: now_millis(),
+        level,
+        message: text.to_string(),
+    };
+    logs.push(id, line.clone());
+    let _ = app.emit(
+        "document-ingest-log",
+        serde_json::json!({ "id": id, "line": line }),
+    );
+}
+
+// 返回某个文档最近的处理日志（最早的在前）
+#[tauri::command]
+pub fn get_document_ingest_log(
+    logs: State<'_, DocumentIngestLogs>,
+    id: String,
+) -> Vec<IngestLogLine> {
+    logs.get(&id)
+}
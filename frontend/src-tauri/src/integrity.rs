@@ -0,0 +1,131 @@
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Notify;
+
+use crate::update::sidecar_path;
+
+// 构建时由 build.rs 计算的后端可执行文件 SHA-256；为空表示构建时没有可执行文件
+const EXPECTED_BACKEND_SHA256: &str = env!("EXPECTED_BACKEND_SHA256");
+// 开发调试用：设置为 1 时跳过校验，便于单独替换后端可执行文件（只在调试构建中生效）
+const SKIP_CHECK_ENV: &str = "DOCUMENT_QA_SKIP_BACKEND_CHECK";
+// 启动后端时等待校验完成的最长时间
+const VERIFY_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+// 后端可执行文件的校验结果；校验完成前（None）不允许启动后端
+pub struct SidecarIntegrity {
+    status: Mutex<Option<Result<(), String>>>,
+    // 校验完成时唤醒正在等待的启动流程
+    ready: Notify,
+}
+
+impl SidecarIntegrity {
+    pub fn new() -> Self {
+        Self {
+            status: Mutex::new(None),
+            ready: Notify::new(),
+        }
+    }
+
+    // 启动后端前调用：校验尚未完成时等待结果，校验失败或等待超时返回错误
+    pub async fn ensure(&self) -> Result<(), String> {
+        let wait = async {
+            loop {
+                // 先登记等待再检查结果，避免错过检查之后才发出的通知
+                let notified = self.ready.notified();
+                if let Some(result) = self.status.lock().unwrap().clone() {
+                    return result;
+                }
+                notified.await;
+            }
+        };
+        tokio::time::timeout(VERIFY_WAIT_TIMEOUT, wait)
+            .await
+            .unwrap_or_else(|_| Err("等待后端可执行文件校验超时".to_string()))
+    }
+
+    // 记录校验结果并唤醒等待中的启动流程
+    fn finish(&self, result: Result<(), String>) {
+        *self.status.lock().unwrap() = Some(result);
+        self.ready.notify_waiters();
+    }
+}
+
+fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// 计算并比较校验值（耗时操作，需在阻塞线程中执行）
+fn check() -> Result<(), String> {
+    if std::env::var(SKIP_CHECK_ENV).is_ok_and(|value| value == "1") {
+        if cfg!(debug_assertions) {
+            log::warn!("已通过 {} 跳过后端可执行文件校验", SKIP_CHECK_ENV);
+            return Ok(());
+        }
+        log::warn!("发布版本忽略 {}，仍然校验后端可执行文件", SKIP_CHECK_ENV);
+    }
+    if EXPECTED_BACKEND_SHA256.is_empty() {
+        log::warn!("构建时未注入后端校验值，跳过校验");
+        return Ok(());
+    }
+    let path = sidecar_path().ok_or("无法确定后端可执行文件的位置")?;
+    let actual =
+        sha256_file(&path).map_err(|e| format!("读取后端可执行文件 {:?} 失败: {}", path, e))?;
+    if actual != EXPECTED_BACKEND_SHA256 {
+        log::error!(
+            "后端可执行文件校验值不匹配：期望 {}，实际 {}",
+            EXPECTED_BACKEND_SHA256,
+            actual
+        );
+        return Err("后端可执行文件校验失败，文件可能已损坏或被篡改，请重新安装应用".to_string());
+    }
+    log::info!("后端可执行文件校验通过");
+    Ok(())
+}
+
+// 在阻塞线程中校验后端可执行文件并记录结果，失败时通知前端
+pub async fn verify(app: &AppHandle) {
+    let result = tauri::async_runtime::spawn_blocking(check)
+        .await
+        .unwrap_or_else(|e| Err(format!("后端校验任务失败: {}", e)));
+    if let Err(message) = &result {
+        let _ = app.emit(
+            "backend-integrity-failed",
+            serde_json::json!({
+                "message": message,
+                "expected": EXPECTED_BACKEND_SHA256,
+            }),
+        );
+    }
+    app.state::<SidecarIntegrity>().finish(result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn ensure_waits_for_pending_verification() {
+        let integrity = Arc::new(SidecarIntegrity::new());
+        let verifier = integrity.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            verifier.finish(Ok(()));
+        });
+        assert_eq!(integrity.ensure().await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn ensure_reports_failed_verification() {
+        let integrity = SidecarIntegrity::new();
+        integrity.finish(Err("校验失败".to_string()));
+        assert_eq!(integrity.ensure().await, Err("校验失败".to_string()));
+    }
+}
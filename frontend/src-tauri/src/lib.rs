@@ -1,125 +1,309 @@
-use std::sync::Mutex;
+mod apiversion;
+mod backend;
+mod backup;
+mod benchmark;
+mod cache;
+mod capabilities;
+mod cgroup;
+mod chat;
+mod config;
+mod credentials;
+mod deeplink;
+mod detached;
+mod device;
+mod diskio;
+mod documents;
+mod eta;
+mod export;
+mod http;
+mod import;
+mod index;
+mod info;
+mod ingestlog;
+mod integrity;
+mod logs;
+mod metrics;
+mod onboarding;
+mod paths;
+mod presets;
+mod processes;
+mod quota;
+mod recent;
+mod relaunch;
+mod requests;
+mod resume;
+mod safemode;
+mod selftest;
+mod sidecar;
+mod support;
+mod tasks;
+mod update;
+mod vault;
+mod warmup;
+mod watchdog;
+mod webhook;
+
+use tauri::webview::PageLoadEvent;
 use tauri::Manager;
-use tauri_plugin_shell::process::{CommandChild, CommandEvent};
-use tauri_plugin_shell::ShellExt;
 
-// 存储后端进程的全局状态
-struct BackendProcess(Mutex<Option<CommandChild>>);
+use apiversion::ApiVersionCache;
+use backend::BackendProcess;
+use backup::BackupState;
+use config::ConfigState;
+use deeplink::DeepLinks;
+use diskio::DiskIoSampler;
+use eta::IndexThroughput;
+use http::BackendClient;
+use import::IngestQueue;
+use index::IndexMaintenance;
+use ingestlog::DocumentIngestLogs;
+use integrity::SidecarIntegrity;
+use logs::BackendLogs;
+use metrics::QueryMetrics;
+use onboarding::Onboarding;
+use paths::AppPaths;
+use quota::VaultQuota;
+use recent::RecentDocuments;
+use relaunch::RelaunchGuard;
+use requests::{PendingRequests, RequestLimiter, RequestTracing};
+use safemode::SafeMode;
+use selftest::SelfTest;
+use update::SidecarVersion;
+use warmup::Warmup;
+use watchdog::MemoryHistory;
+use webhook::Webhook;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // single-instance 需要最先注册：应用已在运行时，新实例把深度链接转交给已有实例后退出
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.set_focus();
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_shell::init())
-        .manage(BackendProcess(Mutex::new(None)))
+        .plugin(tauri_plugin_dialog::init())
+        .manage(BackendProcess::new())
+        .manage(PendingRequests::new())
+        .manage(IndexMaintenance::new())
+        .manage(Warmup::new())
+        .manage(SidecarVersion::new())
+        .manage(RelaunchGuard::new())
+        .manage(SelfTest::new())
+        .manage(DeepLinks::new())
+        .manage(IndexThroughput::new())
+        .manage(SidecarIntegrity::new())
+        .manage(Webhook::new())
+        .manage(DiskIoSampler::new())
+        .manage(DocumentIngestLogs::default())
+        .manage(VaultQuota::default())
+        .manage(MemoryHistory::default())
+        .manage(RequestTracing::new())
+        .manage(ApiVersionCache::new())
+        .manage(SafeMode::from_args())
         .setup(|app| {
             // 日志插件（调试模式）
+            // 初始化失败不影响应用启动，只输出到 stderr
             if cfg!(debug_assertions) {
-                app.handle().plugin(
+                if let Err(e) = app.handle().plugin(
                     tauri_plugin_log::Builder::default()
                         .level(log::LevelFilter::Info)
                         .build(),
-                )?;
+                ) {
+                    eprintln!("日志插件初始化失败，继续启动: {}", e);
+                }
             }
 
-            // 启动后端服务
-            #[cfg(not(debug_assertions))]
-            let shell = app.shell();
-            
+            // 获取用户数据目录并确保目录存在且可写
+            let data_dir = paths::resolve_data_dir();
+            let writable = match std::fs::create_dir_all(&data_dir) {
+                Err(e) => {
+                    log::error!("无法创建数据目录 {:?}: {}", data_dir, e);
+                    paths::prompt_for_data_dir(
+                        app.handle(),
+                        &data_dir,
+                        paths::DATA_DIR_CREATE_FAILED,
+                        &e,
+                    );
+                    false
+                }
+                Ok(_) => match paths::probe_writable(&data_dir) {
+                    Ok(_) => true,
+                    Err(e) => {
+                        log::error!("数据目录不可写 {:?}: {}", data_dir, e);
+                        paths::prompt_for_data_dir(
+                            app.handle(),
+                            &data_dir,
+                            paths::DATA_DIR_READONLY,
+                            &e,
+                        );
+                        false
+                    }
+                },
+            };
+            app.manage(Onboarding::new(&data_dir));
+            app.manage(RecentDocuments::load(&data_dir));
+            app.manage(IngestQueue::load(&data_dir));
+            app.manage(BackupState::load(&data_dir));
+            app.manage(QueryMetrics::new(&data_dir));
+            let config = ConfigState::load(&data_dir);
+            let initial = config.get();
+            app.manage(config);
+            app.manage(RequestLimiter::new(&initial));
+            app.manage(BackendClient::new(&initial.backend_host));
+            let log_dir = paths::resolve_log_dir(&data_dir, initial.log_dir.as_deref());
+            app.manage(BackendLogs::new(&log_dir));
+            logs::start_session_autosave(app.handle());
+            app.manage(AppPaths::new(
+                data_dir,
+                log_dir,
+                initial.active_vault.clone(),
+            ));
+            deeplink::init(app.handle());
+            index::watch_index_model(app.handle());
+            cache::prepare(app.handle());
+            webhook::start(app.handle());
+            backup::start(app.handle());
+
             // 获取后端可执行文件的路径
             // 在开发模式下，从项目根目录运行 Python
             // 在生产模式下，运行打包的可执行文件
-            #[cfg(debug_assertions)]
-            {
+            if !writable {
+                log::warn!("数据目录不可写，暂不启动后端服务");
+                // 仍然校验可执行文件，选择新的数据目录后启动后端时可以直接使用结果
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    integrity::verify(&handle).await;
+                });
+            } else if cfg!(debug_assertions) {
                 log::info!("开发模式：使用 Python 运行后端");
                 // 开发模式下，我们假设用户自己启动后端
                 // 或者可以在这里添加启动 Python 的逻辑
-            }
-
-            #[cfg(not(debug_assertions))]
-            {
-                use std::path::PathBuf;
-                
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    integrity::verify(&handle).await;
+                });
+            } else {
                 log::info!("生产模式：启动打包的后端服务");
-                
-                // 获取用户数据目录
-                let data_dir = if cfg!(target_os = "windows") {
-                    let appdata = std::env::var("APPDATA").unwrap_or_else(|_| {
-                        dirs::home_dir()
-                            .unwrap()
-                            .join("AppData\\Roaming")
-                            .to_string_lossy()
-                            .to_string()
-                    });
-                    PathBuf::from(appdata).join("Document-QA")
-                } else if cfg!(target_os = "macos") {
-                    dirs::home_dir()
-                        .unwrap()
-                        .join("Library")
-                        .join("Application Support")
-                        .join("Document-QA")
-                } else {
-                    dirs::home_dir().unwrap().join(".document-qa")
-                };
-                
-                // 确保目录存在
-                std::fs::create_dir_all(&data_dir).ok();
-                
-                let sidecar = shell
-                    .sidecar("backend")
-                    .expect("无法找到后端可执行文件")
-                    .current_dir(data_dir);
-                
-                match sidecar.spawn() {
-                    Ok((mut rx, child)) => {
-                        log::info!("后端服务已启动");
-                        
-                        // 存储子进程句柄
-                        let state = app.state::<BackendProcess>();
-                        *state.0.lock().unwrap() = Some(child);
-                        
-                        // 异步读取后端输出
-                        tauri::async_runtime::spawn(async move {
-                            while let Some(event) = rx.recv().await {
-                                match event {
-                                    CommandEvent::Stdout(line) => {
-                                        log::info!("[Backend] {}", String::from_utf8_lossy(&line));
-                                    }
-                                    CommandEvent::Stderr(line) => {
-                                        log::warn!("[Backend] {}", String::from_utf8_lossy(&line));
-                                    }
-                                    CommandEvent::Error(err) => {
-                                        log::error!("[Backend] Error: {}", err);
-                                    }
-                                    CommandEvent::Terminated(payload) => {
-                                        log::info!("[Backend] 进程退出: {:?}", payload);
-                                        break;
-                                    }
-                                    _ => {}
-                                }
-                            }
-                        });
+                // 先在后台校验可执行文件，通过后再启动，避免阻塞 setup
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    integrity::verify(&handle).await;
+                    // 上次留下的独立后端仍在运行时直接连接，不再启动新的后端
+                    if detached::reattach(&handle).await {
+                        return;
                     }
-                    Err(e) => {
-                        log::error!("启动后端服务失败: {}", e);
+                    if let Err(e) = backend::spawn_backend(&handle).await {
+                        log::error!("{}", e);
                     }
-                }
+                });
+                watchdog::start(app.handle());
+                watchdog::start_memory_monitor(app.handle());
+                resume::start(app.handle());
+                update::start(app.handle());
+            }
+
+            // 全部初始化完成后再写入首次运行标记
+            if writable {
+                app.state::<Onboarding>().claim();
             }
 
             Ok(())
         })
+        .on_page_load(|webview, payload| {
+            // 前端页面加载完成后才发送 first-run 和 backend-log，避免事件在监听注册前丢失
+            if payload.event() == PageLoadEvent::Finished {
+                webview.state::<Onboarding>().notify(webview.app_handle());
+                if let Some(logs) = webview.try_state::<BackendLogs>() {
+                    logs.set_listening();
+                }
+            }
+        })
+        .invoke_handler(tauri::generate_handler![
+            onboarding::reset_onboarding,
+            config::get_config,
+            config::set_config,
+            config::set_performance_options,
+            config::get_chunking,
+            config::set_chunking,
+            config::set_embedding_provider,
+            config::clear_reindex_needed,
+            config::set_backend_host,
+            config::get_backend_effective_config,
+            credentials::clear_credentials,
+            chat::stream_query,
+            chat::timed_query,
+            metrics::export_metrics,
+            presets::list_presets,
+            presets::save_preset,
+            presets::delete_preset,
+            requests::cancel_request,
+            requests::cancel_all,
+            requests::backend_request,
+            requests::get_in_flight_requests,
+            index::verify_index,
+            index::repair_index,
+            index::compact_index,
+            index::check_index_lock,
+            index::clear_index_lock,
+            index::index_model_matches,
+            eta::get_index_eta,
+            vault::backup_vault,
+            vault::restore_vault,
+            vault::switch_vault,
+            vault::get_vault_stats,
+            backup::get_backup_schedule,
+            backup::set_backup_schedule,
+            cache::clear_cache,
+            backend::get_last_error,
+            backend::dry_run_spawn,
+            processes::list_backend_processes,
+            processes::kill_backend_process,
+            device::get_backend_device,
+            diskio::get_backend_io,
+            logs::get_recent_logs,
+            logs::get_recent_errors,
+            logs::backend_logs_since,
+            logs::export_logs,
+            logs::open_log_dir,
+            logs::set_privacy_mode,
+            support::create_support_bundle,
+            webhook::test_webhook,
+            info::app_info,
+            safemode::start_safe_mode,
+            capabilities::get_platform_capabilities,
+            documents::open_data_dir,
+            import::import_folder,
+            import::pause_ingestion,
+            import::resume_ingestion,
+            import::get_ingestion_status,
+            export::export_answer,
+            warmup::prewarm_backend,
+            update::backend_update_pending,
+            relaunch::relaunch,
+            selftest::run_self_test,
+            documents::reveal_document,
+            documents::open_document_at,
+            recent::get_recent_documents,
+            recent::clear_recent_documents,
+            detached::stop_detached_backend,
+            sidecar::set_custom_backend,
+            benchmark::run_embedding_benchmark,
+            requests::set_request_tracing,
+            requests::get_request_trace,
+            tasks::list_backend_tasks,
+            tasks::cancel_backend_task,
+            apiversion::get_backend_api_version,
+            ingestlog::get_document_ingest_log,
+        ])
         .on_window_event(|window, event| {
-            // 窗口关闭时终止后端进程
+            // 窗口关闭时终止后端进程（独立运行的后端除外），同时中止仍在进行的启动流程
             if let tauri::WindowEvent::CloseRequested { .. } = event {
                 log::info!("窗口关闭，正在终止后端服务...");
-                let state = window.state::<BackendProcess>();
-                // 先获取 child，释放锁后再使用
-                let child_option = { state.0.lock().unwrap().take() };
-                if let Some(child) = child_option {
-                    match child.kill() {
-                        Ok(_) => log::info!("后端服务已终止"),
-                        Err(e) => log::error!("终止后端服务失败: {}", e),
-                    }
-                }
+                backend::shutdown_backend(window.app_handle());
+                window.state::<BackendLogs>().flush_session();
             }
         })
         .run(tauri::generate_context!())
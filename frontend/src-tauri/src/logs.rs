@@ -0,0 +1,666 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::backend::{now_millis, BackendFailure, BackendProcess};
+use crate::detached;
+use crate::documents::open_with_default;
+use crate::ingestlog::{self, DocumentIngestLogs};
+use crate::paths::AppPaths;
+use crate::tasks;
+
+// 内存中保留的后端日志行数
+const BUFFER_CAPACITY: usize = 1000;
+// 后端日志文件名
+const LOG_FILE: &str = "backend.log";
+// 启动时日志文件超过该大小则轮转为 backend.log.1
+const MAX_LOG_FILE_BYTES: u64 = 10 * 1024 * 1024;
+// 环形缓冲区的定期快照，硬崩溃（如 SIGKILL）后仍能看到本次会话最后的日志
+const SESSION_FILE: &str = "session.log";
+// 环形缓冲区写入 session.log 的间隔
+const SESSION_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+// 打开（必要时先轮转）backend.log，失败时只记录到内存
+fn open_log_file(log_dir: &Path) -> Option<File> {
+    let path = log_dir.join(LOG_FILE);
+    if std::fs::metadata(&path).is_ok_and(|m| m.len() > MAX_LOG_FILE_BYTES) {
+        let _ = std::fs::rename(&path, log_dir.join(format!("{}.1", LOG_FILE)));
+    }
+    match OpenOptions::new().create(true).append(true).open(&path) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            log::warn!("无法打开日志文件 {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+// 每秒超过该行数时进入抽样模式
+const THROTTLE_LINES_PER_SEC: u64 = 200;
+// 抽样模式下每 N 行保留 1 行（error 级别不受影响）
+const THROTTLE_SAMPLE_EVERY: u64 = 50;
+// 抽样模式下汇报省略行数的间隔
+const THROTTLE_SUMMARY_INTERVAL: Duration = Duration::from_secs(5);
+
+// 根据输出速率决定是否抽样记录，避免大量导入时日志写入成为瓶颈
+struct Throttle {
+    window_start: Instant,
+    window_lines: u64,
+    active: bool,
+    seen: u64,
+    suppressed: u64,
+    last_summary: Instant,
+}
+
+// 对一行输出的处理结果
+struct Admission {
+    keep: bool,
+    // 进入（true）或退出（false）抽样模式
+    changed: Option<bool>,
+    // 需要汇报的省略行数
+    suppressed: Option<u64>,
+}
+
+impl Throttle {
+    fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            window_start: now,
+            window_lines: 0,
+            active: false,
+            seen: 0,
+            suppressed: 0,
+            last_summary: now,
+        }
+    }
+
+    fn admit(&mut self, always_keep: bool) -> Admission {
+        let now = Instant::now();
+        let mut changed = None;
+        let mut suppressed = None;
+        self.window_lines += 1;
+        let elapsed = now.duration_since(self.window_start);
+        if elapsed >= Duration::from_secs(1) {
+            let rate = self.window_lines * 1000 / (elapsed.as_millis() as u64).max(1);
+            self.window_start = now;
+            self.window_lines = 0;
+            // 速率降到阈值一半以下才退出，避免在阈值附近反复切换
+            if self.active && rate < THROTTLE_LINES_PER_SEC / 2 {
+                self.active = false;
+                changed = Some(false);
+                suppressed = Some(std::mem::take(&mut self.suppressed));
+            }
+        } else if !self.active && self.window_lines > THROTTLE_LINES_PER_SEC {
+            self.active = true;
+            self.seen = 0;
+            self.last_summary = now;
+            changed = Some(true);
+        }
+
+        if !self.active {
+            return Admission {
+                keep: true,
+                changed,
+                suppressed: suppressed.filter(|n| *n > 0),
+            };
+        }
+        if now.duration_since(self.last_summary) >= THROTTLE_SUMMARY_INTERVAL {
+            self.last_summary = now;
+            suppressed = Some(std::mem::take(&mut self.suppressed));
+        }
+        self.seen += 1;
+        let keep = always_keep || self.seen % THROTTLE_SAMPLE_EVERY == 1;
+        if !keep {
+            self.suppressed += 1;
+        }
+        Admission {
+            keep,
+            changed,
+            suppressed: suppressed.filter(|n| *n > 0),
+        }
+    }
+}
+
+// 启动时把上一次会话的 session.log 轮转为 session.log.1，保留上次（可能崩溃的）会话的日志
+fn rotate_session_file(log_dir: &Path) -> PathBuf {
+    let path = log_dir.join(SESSION_FILE);
+    if path.exists() {
+        let _ = std::fs::rename(&path, log_dir.join(format!("{}.1", SESSION_FILE)));
+    }
+    path
+}
+
+fn format_line(line: &LogLine) -> String {
+    format!(
+        "{} [{}] [{}] {}",
+        line.timestamp, line.level, line.source, line.message
+    )
+}
+
+// 一行后端输出，seq 单调递增，用于增量拉取
+#[derive(Debug, Clone, Serialize)]
+pub struct LogLine {
+    pub seq: u64,
+    // Unix 时间戳（毫秒）
+    pub timestamp: u64,
+    pub level: &'static str,
+    // 来源：stdout、stderr，或 http（开启 log_http 或请求追踪时记录的请求）
+    pub source: &'static str,
+    pub message: String,
+}
+
+struct LogBuffer {
+    lines: VecDeque<LogLine>,
+    next_seq: u64,
+}
+
+// 后端日志：内存中的环形缓冲区，同时追加写入日志目录下的 backend.log
+pub struct BackendLogs {
+    buffer: Mutex<LogBuffer>,
+    file: Mutex<Option<File>>,
+    // 前端页面是否已加载完成；此前不推送 backend-log 事件，页面加载后通过 get_recent_logs 补齐
+    listening: AtomicBool,
+    session: PathBuf,
+    // 已写入 session.log 的最大序号，没有新日志时跳过写入
+    flushed_seq: AtomicU64,
+    // 隐私模式：不写日志文件、不缓冲、不推送事件；只在本次运行内有效
+    privacy: AtomicBool,
+    throttle: Mutex<Throttle>,
+}
+
+impl BackendLogs {
+    pub fn new(log_dir: &Path) -> Self {
+        Self {
+            buffer: Mutex::new(LogBuffer {
+                lines: VecDeque::with_capacity(BUFFER_CAPACITY),
+                next_seq: 1,
+            }),
+            file: Mutex::new(open_log_file(log_dir)),
+            listening: AtomicBool::new(false),
+            session: rotate_session_file(log_dir),
+            flushed_seq: AtomicU64::new(0),
+            privacy: AtomicBool::new(false),
+            throttle: Mutex::new(Throttle::new()),
+        }
+    }
+
+    pub fn privacy_mode(&self) -> bool {
+        self.privacy.load(Ordering::SeqCst)
+    }
+
+    // 开启隐私模式时清空环形缓冲区和 session.log 快照
+    fn set_privacy_mode(&self, enabled: bool) {
+        self.privacy.store(enabled, Ordering::SeqCst);
+        if !enabled {
+            return;
+        }
+        let mut buffer = self.buffer.lock().unwrap();
+        buffer.lines.clear();
+        self.flushed_seq
+            .store(buffer.next_seq - 1, Ordering::SeqCst);
+        if let Err(e) = std::fs::write(&self.session, "") {
+            log::warn!("清空 {:?} 失败: {}", self.session, e);
+        }
+    }
+
+    // 清除本次和上次会话的日志快照（session.log 及轮转的 session.log.1）和环形缓冲区，
+    // 之后只记录新的输出。backend.log 保留，其中的敏感内容由 redact_secret 抹掉
+    pub fn remove_session_files(&self) {
+        let mut buffer = self.buffer.lock().unwrap();
+        buffer.lines.clear();
+        self.flushed_seq
+            .store(buffer.next_seq - 1, Ordering::SeqCst);
+        let rotated = self.session.with_file_name(format!("{}.1", SESSION_FILE));
+        for path in [&self.session, &rotated] {
+            match std::fs::remove_file(path) {
+                Ok(_) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => log::warn!("删除 {:?} 失败: {}", path, e),
+            }
+        }
+    }
+
+    // 把 backend.log 的缓冲写入磁盘并刷新 session.log，用于导出日志前
+    pub fn flush_files(&self) {
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            let _ = file.flush();
+        }
+        self.flush_session();
+    }
+
+    // 把环形缓冲区的当前内容整体写入 session.log（先写临时文件再重命名）
+    pub fn flush_session(&self) {
+        if self.privacy_mode() {
+            return;
+        }
+        let (text, last_seq) = {
+            let buffer = self.buffer.lock().unwrap();
+            let last_seq = buffer.next_seq - 1;
+            if last_seq == self.flushed_seq.load(Ordering::SeqCst) {
+                return;
+            }
+            let mut text = String::new();
+            for line in &buffer.lines {
+                text.push_str(&format_line(line));
+                text.push('\n');
+            }
+            (text, last_seq)
+        };
+        let tmp = self.session.with_extension("log.tmp");
+        let result = std::fs::write(&tmp, text).and_then(|_| std::fs::rename(&tmp, &self.session));
+        match result {
+            Ok(_) => self.flushed_seq.store(last_seq, Ordering::SeqCst),
+            Err(e) => log::warn!("写入 {:?} 失败: {}", self.session, e),
+        }
+    }
+
+    // 前端页面加载完成后开始推送 backend-log 事件
+    pub fn set_listening(&self) {
+        self.listening.store(true, Ordering::SeqCst);
+    }
+
+    fn write_file(&self, line: &LogLine) {
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            let _ = writeln!(file, "{}", format_line(line));
+        }
+    }
+
+    fn push(&self, source: &'static str, level: &'static str, message: String) -> LogLine {
+        let mut buffer = self.buffer.lock().unwrap();
+        let line = LogLine {
+            seq: buffer.next_seq,
+            timestamp: now_millis(),
+            level,
+            source,
+            message,
+        };
+        buffer.next_seq += 1;
+        if buffer.lines.len() == BUFFER_CAPACITY {
+            buffer.lines.pop_front();
+        }
+        buffer.lines.push_back(line.clone());
+        line
+    }
+
+    // 返回序号大于 seq 的日志以及当前最大序号
+    fn since(&self, seq: u64) -> (Vec<LogLine>, u64) {
+        let buffer = self.buffer.lock().unwrap();
+        let lines = buffer
+            .lines
+            .iter()
+            .filter(|line| line.seq > seq)
+            .cloned()
+            .collect();
+        (lines, buffer.next_seq - 1)
+    }
+
+    fn recent(&self, limit: usize) -> Vec<LogLine> {
+        let buffer = self.buffer.lock().unwrap();
+        let skip = buffer.lines.len().saturating_sub(limit);
+        buffer.lines.iter().skip(skip).cloned().collect()
+    }
+
+    // 最近的 stderr 输出（最多 limit 行，按时间顺序）
+    fn recent_errors(&self, limit: usize) -> Vec<LogLine> {
+        let buffer = self.buffer.lock().unwrap();
+        let mut lines: Vec<LogLine> = buffer
+            .lines
+            .iter()
+            .rev()
+            .filter(|line| line.source == "stderr")
+            .take(limit)
+            .cloned()
+            .collect();
+        lines.reverse();
+        lines
+    }
+
+    // 缓冲区中包含指定文本的日志（最早的在前）
+    pub fn matching(&self, needle: &str) -> Vec<LogLine> {
+        let buffer = self.buffer.lock().unwrap();
+        buffer
+            .lines
+            .iter()
+            .filter(|line| line.message.contains(needle))
+            .cloned()
+            .collect()
+    }
+}
+
+// 按内容判断一行 stderr 输出的级别。Python 的 logging 和 uvicorn 默认把所有日志
+// （包括 INFO）都写到 stderr，不能仅凭来源当作错误；异常堆栈的各行都按 error 处理
+pub fn stderr_level(line: &str) -> &'static str {
+    let trimmed = line.trim_start();
+    let has_level =
+        |level: &str| trimmed.starts_with(level) || trimmed.contains(&format!(" {} ", level));
+    let exception_line = trimmed.split_once(':').is_some_and(|(name, _)| {
+        !name.contains(char::is_whitespace)
+            && (name.ends_with("Error") || name.ends_with("Exception"))
+    });
+    if has_level("ERROR")
+        || has_level("CRITICAL")
+        || trimmed.starts_with("Traceback (most recent call last)")
+        || trimmed.starts_with("File \"")
+        || exception_line
+    {
+        "error"
+    } else if has_level("WARNING") {
+        "warn"
+    } else {
+        "info"
+    }
+}
+
+// 记录一行后端输出：写入应用日志、环形缓冲区，并实时推送 backend-log 事件。
+// source 标记输出来自 stdout 还是 stderr。输出过快时进入抽样模式并定期汇报省略的行数
+pub fn record(app: &AppHandle, source: &'static str, level: &'static str, message: String) {
+    let logs = app.state::<BackendLogs>();
+    // 隐私模式下后端输出直接丢弃
+    if logs.privacy_mode() {
+        return;
+    }
+    // 文档处理日志和任务状态在抽样之前处理，保证单个文档的记录和任务状态完整
+    ingestlog::capture(app, &app.state::<DocumentIngestLogs>(), level, &message);
+    tasks::capture(app, &message);
+
+    // 输出过快时只抽样记录，error 级别总是保留
+    let admission = logs.throttle.lock().unwrap().admit(level == "error");
+    if let Some(active) = admission.changed {
+        if active {
+            log::warn!(
+                "后端输出过快（每秒超过 {} 行），改为抽样记录",
+                THROTTLE_LINES_PER_SEC
+            );
+        } else {
+            log::info!("后端输出速率已恢复，恢复完整记录");
+        }
+        let _ = app.emit("log-throttled", serde_json::json!({ "throttled": active }));
+    }
+    if let Some(suppressed) = admission.suppressed {
+        store(
+            app,
+            &logs,
+            source,
+            "info",
+            format!("（已省略 {} 行输出）", suppressed),
+        );
+    }
+    if admission.keep {
+        store(app, &logs, source, level, message);
+    }
+}
+
+fn store(
+    app: &AppHandle,
+    logs: &BackendLogs,
+    source: &'static str,
+    level: &'static str,
+    message: String,
+) {
+    match level {
+        "error" => log::error!("[Backend] {}", message),
+        "warn" => log::warn!("[Backend] {}", message),
+        _ => log::info!("[Backend] {}", message),
+    }
+    let line = logs.push(source, level, message);
+    logs.write_file(&line);
+    // 没有窗口（例如启动画面阶段或窗口已关闭）时不推送事件，避免事件无限堆积
+    if logs.listening.load(Ordering::SeqCst) && !app.webview_windows().is_empty() {
+        let _ = app.emit("backend-log", &line);
+    }
+}
+
+// 定期把环形缓冲区写入 session.log，直到应用开始退出（退出时由关闭流程再写一次）
+pub fn start_session_autosave(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SESSION_FLUSH_INTERVAL).await;
+            if app.state::<BackendProcess>().shutting_down() {
+                break;
+            }
+            app.state::<BackendLogs>().flush_session();
+        }
+    });
+}
+
+// 看起来像凭据的 JSON 字段名（小写比较）
+const SECRET_FIELD_HINTS: [&str; 6] = [
+    "key",
+    "token",
+    "secret",
+    "password",
+    "authorization",
+    "cookie",
+];
+// 记录 HTTP 请求/响应体时保留的最大字符数
+const MAX_LOGGED_BODY_CHARS: usize = 512;
+
+// 把 JSON 中看起来像凭据的字段替换为 ***
+pub fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (field, value) in map.iter_mut() {
+                let field = field.to_lowercase();
+                if SECRET_FIELD_HINTS.iter().any(|hint| field.contains(hint)) {
+                    *value = Value::String("***".to_string());
+                } else {
+                    redact_json(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+// 把请求/响应体转为适合写入日志的文本：隐去凭据字段并截断过长的内容
+pub fn summarize_body(body: &Value) -> String {
+    let mut body = body.clone();
+    redact_json(&mut body);
+    let text = body.to_string();
+    if text.chars().count() <= MAX_LOGGED_BODY_CHARS {
+        return text;
+    }
+    let truncated: String = text.chars().take(MAX_LOGGED_BODY_CHARS).collect();
+    format!("{}…（共 {} 字符）", truncated, text.chars().count())
+}
+
+// 日志目录中由应用写入的文件：backend.log、session.log（含轮转的旧文件）和崩溃报告
+pub fn is_log_file(name: &str) -> bool {
+    name.starts_with(LOG_FILE)
+        || name.starts_with(detached::DETACHED_LOG_FILE)
+        || name.starts_with(SESSION_FILE)
+        || (name.starts_with("crash-") && name.ends_with(".json"))
+}
+
+// 把敏感内容（如 API 密钥）从内存中的日志行、backend.log、session.log 和崩溃报告中抹掉
+pub fn redact_secret(app: &AppHandle, secret: &str) {
+    const REDACTED: &str = "[已清除]";
+    if secret.is_empty() {
+        return;
+    }
+    let logs = app.state::<BackendLogs>();
+    for line in logs.buffer.lock().unwrap().lines.iter_mut() {
+        if line.message.contains(secret) {
+            line.message = line.message.replace(secret, REDACTED);
+        }
+    }
+
+    let log_dir = &app.state::<AppPaths>().log_dir;
+    let Ok(entries) = std::fs::read_dir(log_dir) else {
+        return;
+    };
+    // 重写期间关闭 backend.log 的句柄，完成后重新打开
+    let mut file = logs.file.lock().unwrap();
+    *file = None;
+    for entry in entries.flatten() {
+        if !is_log_file(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        let Ok(bytes) = std::fs::read(entry.path()) else {
+            continue;
+        };
+        let text = String::from_utf8_lossy(&bytes);
+        if text.contains(secret) {
+            if let Err(e) = std::fs::write(entry.path(), text.replace(secret, REDACTED)) {
+                log::error!("清除日志 {:?} 中的敏感内容失败: {}", entry.path(), e);
+            }
+        }
+    }
+    *file = open_log_file(log_dir);
+}
+
+// 获取最近的后端日志（默认全部缓冲的日志）
+#[tauri::command]
+pub fn get_recent_logs(logs: State<'_, BackendLogs>, limit: Option<usize>) -> Vec<LogLine> {
+    logs.recent(limit.unwrap_or(BUFFER_CAPACITY))
+}
+
+// 获取最近的 stderr 输出，用于界面上单独的“问题”面板
+#[tauri::command]
+pub fn get_recent_errors(logs: State<'_, BackendLogs>, limit: Option<usize>) -> Vec<LogLine> {
+    logs.recent_errors(limit.unwrap_or(BUFFER_CAPACITY))
+}
+
+// 增量获取序号大于 seq 的日志，返回日志和新的最大序号
+#[tauri::command]
+pub fn backend_logs_since(logs: State<'_, BackendLogs>, seq: u64) -> (Vec<LogLine>, u64) {
+    logs.since(seq)
+}
+
+// 后端失败时在日志目录写入崩溃报告，包含失败详情和 stderr 末尾
+pub fn write_crash_report(app: &AppHandle, failure: &BackendFailure) {
+    if app.state::<BackendLogs>().privacy_mode() {
+        log::info!("隐私模式已开启，不写入崩溃报告");
+        return;
+    }
+    let log_dir = &app.state::<AppPaths>().log_dir;
+    let path = log_dir.join(format!("crash-{}.json", failure.timestamp));
+    let result = serde_json::to_string_pretty(failure)
+        .map_err(|e| e.to_string())
+        .and_then(|text| std::fs::write(&path, text).map_err(|e| e.to_string()));
+    match result {
+        Ok(_) => log::info!("崩溃报告已写入 {:?}", path),
+        Err(e) => log::error!("写入崩溃报告失败: {}", e),
+    }
+}
+
+// 把 backend.log 导出到用户选择的位置，取消时返回 None
+#[tauri::command]
+pub async fn export_logs(app: AppHandle) -> Result<Option<String>, String> {
+    let source = app.state::<AppPaths>().log_dir.join(LOG_FILE);
+    let Some(chosen) = app
+        .dialog()
+        .file()
+        .add_filter("log", &["log"])
+        .set_file_name("document-qa-backend.log")
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+    let dest: PathBuf = chosen
+        .into_path()
+        .map_err(|e| format!("无效的保存路径: {}", e))?;
+
+    // 先刷新文件句柄，确保导出内容包含最新的日志
+    if let Some(file) = app.state::<BackendLogs>().file.lock().unwrap().as_mut() {
+        let _ = file.flush();
+    }
+    std::fs::copy(&source, &dest).map_err(|e| format!("导出日志失败: {}", e))?;
+    log::info!("日志已导出到 {:?}", dest);
+    Ok(Some(dest.to_string_lossy().to_string()))
+}
+
+// 开启或关闭隐私模式：开启时停止写入后端日志、清空内存中的日志并不再推送 backend-log 事件。
+// 重启应用后总是恢复为关闭
+#[tauri::command]
+pub fn set_privacy_mode(logs: State<'_, BackendLogs>, enabled: bool) {
+    logs.set_privacy_mode(enabled);
+    if enabled {
+        log::info!("隐私模式已开启");
+    } else {
+        log::info!("隐私模式已关闭，恢复记录日志");
+    }
+}
+
+// 在文件管理器中打开日志目录
+#[tauri::command]
+pub fn open_log_dir(paths: State<'_, AppPaths>) -> Result<(), String> {
+    open_with_default(&paths.log_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_session_files_deletes_current_and_rotated_snapshots() {
+        let dir = std::env::temp_dir().join(format!("document-qa-session-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        // 上次会话留下的 session.log 在启动时轮转为 session.log.1
+        std::fs::write(dir.join(SESSION_FILE), "previous session").unwrap();
+        let logs = BackendLogs::new(&dir);
+        std::fs::write(dir.join(SESSION_FILE), "current session").unwrap();
+
+        logs.remove_session_files();
+        assert!(!dir.join(SESSION_FILE).exists());
+        assert!(!dir.join(format!("{}.1", SESSION_FILE)).exists());
+        assert!(logs.recent(BUFFER_CAPACITY).is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn python_log_levels_are_classified() {
+        assert_eq!(
+            stderr_level("INFO:     Uvicorn running on http://127.0.0.1:12345"),
+            "info"
+        );
+        assert_eq!(
+            stderr_level("WARNING:app.services:slow embedding batch"),
+            "warn"
+        );
+        assert_eq!(
+            stderr_level("ERROR:    Exception in ASGI application"),
+            "error"
+        );
+        assert_eq!(stderr_level("CRITICAL:root:database is locked"), "error");
+        assert_eq!(
+            stderr_level("2026-01-01 12:00:00 - app - ERROR - failed"),
+            "error"
+        );
+    }
+
+    #[test]
+    fn traceback_lines_are_errors() {
+        assert_eq!(stderr_level("Traceback (most recent call last):"), "error");
+        assert_eq!(
+            stderr_level("  File \"/app/main.py\", line 10, in <module>"),
+            "error"
+        );
+        assert_eq!(
+            stderr_level("ValueError: chunk_size must be positive"),
+            "error"
+        );
+        assert_eq!(
+            stderr_level("sqlite3.OperationalError: database is locked"),
+            "error"
+        );
+    }
+
+    #[test]
+    fn plain_output_is_info() {
+        assert_eq!(stderr_level("Loading embedding model..."), "info");
+        assert_eq!(stderr_level("progress: 50%"), "info");
+        assert_eq!(stderr_level(""), "info");
+    }
+}
@@ -0,0 +1,179 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::now_millis;
+use crate::config::ConfigState;
+use crate::logs::BackendLogs;
+
+// 数据目录中保存提问统计的文件，每行一条 JSON 记录，只追加
+const METRICS_FILE: &str = "metrics.jsonl";
+// 超过该大小时轮转为 metrics.jsonl.1（只保留一份旧文件）
+const MAX_METRICS_BYTES: u64 = 5 * 1024 * 1024;
+
+// 一次提问的统计：时间、耗时分布、检索到的切片数和 token 用量。
+// 默认不包含问题内容，只有开启 include_query_text 时才记录
+#[derive(Debug, Serialize)]
+pub struct QueryMetric {
+    // Unix 时间戳（毫秒）
+    pub timestamp: u64,
+    // stream（流式提问）或 timed（timed_query）
+    pub kind: &'static str,
+    // ok、error 或 cancelled
+    pub outcome: &'static str,
+    pub preset: Option<String>,
+    // 后端报告的检索和生成耗时（毫秒），旧后端没有
+    pub retrieval_ms: Option<u64>,
+    pub generation_ms: Option<u64>,
+    // 流式提问收到第一段回答的时间（毫秒）
+    pub first_token_ms: Option<u64>,
+    pub total_ms: u64,
+    pub retrieved_chunks: Option<usize>,
+    pub prompt_tokens: Option<u64>,
+    pub completion_tokens: Option<u64>,
+    pub total_tokens: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    #[serde(skip)]
+    started: Instant,
+}
+
+impl QueryMetric {
+    pub fn start(kind: &'static str, preset: Option<String>) -> Self {
+        Self {
+            timestamp: now_millis(),
+            kind,
+            outcome: "ok",
+            preset,
+            retrieval_ms: None,
+            generation_ms: None,
+            first_token_ms: None,
+            total_ms: 0,
+            retrieved_chunks: None,
+            prompt_tokens: None,
+            completion_tokens: None,
+            total_tokens: None,
+            query: None,
+            started: Instant::now(),
+        }
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+
+    pub fn mark_first_token(&mut self) {
+        if self.first_token_ms.is_none() {
+            self.first_token_ms = Some(self.elapsed_ms());
+        }
+    }
+
+    pub fn set_sources(&mut self, sources: &Value) {
+        self.retrieved_chunks = sources.as_array().map(Vec::len);
+    }
+
+    // 读取后端返回的 usage 和 timing（提问响应或流式的 done 事件）
+    pub fn set_response(&mut self, response: &Value) {
+        let usage = &response["usage"];
+        self.prompt_tokens = usage["prompt_tokens"].as_u64();
+        self.completion_tokens = usage["completion_tokens"].as_u64();
+        self.total_tokens = usage["total_tokens"].as_u64();
+        let timing = &response["timing"];
+        self.retrieval_ms = timing["retrieval_ms"].as_u64();
+        self.generation_ms = timing["generation_ms"].as_u64();
+    }
+
+    pub fn finish(&mut self, outcome: &'static str) {
+        self.outcome = outcome;
+        self.total_ms = self.elapsed_ms();
+    }
+}
+
+// 提问统计文件，写入时加锁保证每条记录完整地占一行
+pub struct QueryMetrics {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl QueryMetrics {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            path: data_dir.join(METRICS_FILE),
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn rotated_path(&self) -> PathBuf {
+        self.path.with_extension("jsonl.1")
+    }
+
+    fn append(&self, metric: &QueryMetric) -> Result<(), String> {
+        let mut line = serde_json::to_string(metric).map_err(|e| e.to_string())?;
+        line.push('\n');
+        let _lock = self.lock.lock().unwrap();
+        if std::fs::metadata(&self.path).is_ok_and(|meta| meta.len() > MAX_METRICS_BYTES) {
+            let _ = std::fs::rename(&self.path, self.rotated_path());
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| format!("写入 {:?} 失败: {}", self.path, e))
+    }
+}
+
+// 开启 collect_metrics 时记录一次提问。问题内容只在开启 include_query_text 且不在隐私模式下记录
+pub fn record(app: &AppHandle, mut metric: QueryMetric, query: &str) {
+    let config = app.state::<ConfigState>().get();
+    if !config.collect_metrics {
+        return;
+    }
+    if config.include_query_text && !app.state::<BackendLogs>().privacy_mode() {
+        metric.query = Some(query.to_string());
+    }
+    if let Err(e) = app.state::<QueryMetrics>().append(&metric) {
+        log::warn!("记录提问统计失败: {}", e);
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct MetricsExport {
+    pub path: String,
+    pub records: usize,
+}
+
+// 把提问统计（包括轮转的旧文件，按时间顺序）导出为 dest 文件，返回导出的记录数
+#[tauri::command]
+pub fn export_metrics(
+    metrics: State<'_, QueryMetrics>,
+    dest: String,
+) -> Result<MetricsExport, String> {
+    let dest = PathBuf::from(dest);
+    let mut contents = String::new();
+    {
+        let _lock = metrics.lock.lock().unwrap();
+        for path in [metrics.rotated_path(), metrics.path.clone()] {
+            match std::fs::read_to_string(&path) {
+                Ok(text) => contents.push_str(&text),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("读取 {:?} 失败: {}", path, e)),
+            }
+        }
+    }
+    if contents.is_empty() {
+        return Err("还没有提问统计，请先在设置中开启 collect_metrics".to_string());
+    }
+    std::fs::write(&dest, &contents).map_err(|e| format!("导出提问统计失败: {}", e))?;
+    let records = contents.lines().filter(|line| !line.is_empty()).count();
+    log::info!("已导出 {} 条提问统计到 {:?}", records, dest);
+    Ok(MetricsExport {
+        path: dest.to_string_lossy().to_string(),
+        records,
+    })
+}
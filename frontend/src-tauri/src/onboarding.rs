@@ -0,0 +1,69 @@
+use std::fs::OpenOptions;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, State};
+
+// 数据目录中的首次运行标记文件
+const MARKER_FILE: &str = ".initialized";
+
+// 首次运行（新手引导）状态
+pub struct Onboarding {
+    marker: PathBuf,
+    // 是否还有尚未发送给前端的 first-run 事件
+    pending: AtomicBool,
+}
+
+impl Onboarding {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            marker: data_dir.join(MARKER_FILE),
+            pending: AtomicBool::new(false),
+        }
+    }
+
+    // 尝试原子地创建标记文件；只有真正创建成功的那一次才算首次运行，
+    // 多个实例同时启动时也只会有一个判定为首次运行
+    pub fn claim(&self) -> bool {
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&self.marker)
+        {
+            Ok(_) => {
+                log::info!("检测到首次运行，已创建标记文件: {:?}", self.marker);
+                self.pending.store(true, Ordering::SeqCst);
+                true
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => false,
+            Err(e) => {
+                log::error!("创建首次运行标记失败: {}", e);
+                false
+            }
+        }
+    }
+
+    // 页面加载完成后调用：如有待发送的首次运行事件则发送给前端
+    pub fn notify(&self, app: &AppHandle) {
+        if self.pending.swap(false, Ordering::SeqCst) {
+            if let Err(e) = app.emit("first-run", ()) {
+                log::error!("发送 first-run 事件失败: {}", e);
+            }
+        }
+    }
+}
+
+// 重新触发新手引导（供技术支持使用，前端界面不直接暴露）
+#[tauri::command]
+pub fn reset_onboarding(app: AppHandle, onboarding: State<'_, Onboarding>) -> Result<(), String> {
+    match std::fs::remove_file(&onboarding.marker) {
+        Ok(_) => {}
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(format!("删除首次运行标记失败: {}", e)),
+    }
+    log::info!("已重置新手引导");
+    if onboarding.claim() {
+        onboarding.notify(&app);
+    }
+    Ok(())
+}
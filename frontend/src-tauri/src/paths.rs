@@ -0,0 +1,277 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+// 构建渠道，编译时通过环境变量 RELEASE_CHANNEL 指定（如 beta），默认 stable
+pub const RELEASE_CHANNEL: &str = match option_env!("RELEASE_CHANNEL") {
+    Some(channel) => channel,
+    None => "stable",
+};
+
+// 记录用户自选数据目录的文件名（放在系统配置目录下，而不是数据目录内，
+// 这样即使默认数据目录不可写也能保存选择）
+const LOCATION_FILE: &str = "document-qa-location";
+
+// 非 stable 渠道在目录名后追加渠道名，避免与正式版共用数据；
+// stable 保持原有路径不变以兼容已有安装
+fn channel_name(base: &str) -> String {
+    if RELEASE_CHANNEL == "stable" {
+        base.to_string()
+    } else {
+        format!("{}-{}", base, RELEASE_CHANNEL)
+    }
+}
+
+// 默认的用户数据目录（与后端 config.py 中打包环境的路径保持一致）
+fn default_data_dir() -> PathBuf {
+    if cfg!(target_os = "windows") {
+        let appdata = std::env::var("APPDATA").unwrap_or_else(|_| {
+            dirs::home_dir()
+                .unwrap()
+                .join("AppData\\Roaming")
+                .to_string_lossy()
+                .to_string()
+        });
+        PathBuf::from(appdata).join(channel_name("Document-QA"))
+    } else if cfg!(target_os = "macos") {
+        dirs::home_dir()
+            .unwrap()
+            .join("Library")
+            .join("Application Support")
+            .join(channel_name("Document-QA"))
+    } else {
+        dirs::home_dir().unwrap().join(channel_name(".document-qa"))
+    }
+}
+
+fn location_file() -> Option<PathBuf> {
+    dirs::config_local_dir().map(|dir| dir.join(channel_name(LOCATION_FILE)))
+}
+
+// 获取用户数据目录：优先使用用户选择过的目录，否则使用默认目录
+pub fn resolve_data_dir() -> PathBuf {
+    location_file()
+        .and_then(|file| std::fs::read_to_string(file).ok())
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(default_data_dir)
+}
+
+// 保存用户选择的数据目录，下次启动时生效
+fn persist_data_dir(dir: &Path) -> io::Result<()> {
+    let file = location_file()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "无法确定系统配置目录"))?;
+    if let Some(parent) = file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(file, dir.to_string_lossy().as_bytes())
+}
+
+// 写入探测：创建并删除一个临时文件，确认目录确实可写
+pub fn probe_writable(dir: &Path) -> io::Result<()> {
+    let probe = dir.join(format!(".write-probe-{}", std::process::id()));
+    std::fs::write(&probe, b"probe")?;
+    std::fs::remove_file(&probe)
+}
+
+// 数据目录无法创建时使用的提示
+pub const DATA_DIR_CREATE_FAILED: &str = "无法创建数据目录";
+// 数据目录不可写时使用的提示
+pub const DATA_DIR_READONLY: &str = "数据目录不可写";
+
+// 数据目录不可用（无法创建或不可写）时通知前端，并弹窗让用户选择一个可写的目录
+pub fn prompt_for_data_dir(app: &AppHandle, dir: &Path, reason: &str, error: &io::Error) {
+    let event = if reason == DATA_DIR_CREATE_FAILED {
+        "data-dir-create-failed"
+    } else {
+        "data-dir-readonly"
+    };
+    let _ = app.emit(
+        event,
+        serde_json::json!({
+            "path": dir.to_string_lossy(),
+            "error": error.to_string(),
+        }),
+    );
+
+    let handle = app.clone();
+    app.dialog()
+        .message(format!(
+            "{}：\n{}\n\n{}\n\n请选择一个可写的目录用于保存文档和索引。",
+            reason,
+            dir.display(),
+            error
+        ))
+        .title("数据目录不可用")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "选择目录".into(),
+            "退出".into(),
+        ))
+        .show(move |choose| {
+            if choose {
+                pick_data_dir(handle);
+            } else {
+                handle.exit(1);
+            }
+        });
+}
+
+fn pick_data_dir(app: AppHandle) {
+    let handle = app.clone();
+    app.dialog().file().pick_folder(move |folder| {
+        let Some(dir) = folder.and_then(|f| f.into_path().ok()) else {
+            handle.exit(1);
+            return;
+        };
+        if let Err(e) = probe_writable(&dir) {
+            log::error!("所选目录不可写 {:?}: {}", dir, e);
+            prompt_for_data_dir(&handle, &dir, DATA_DIR_READONLY, &e);
+            return;
+        }
+        match persist_data_dir(&dir) {
+            Ok(_) => {
+                log::info!("数据目录已切换为 {:?}，正在重新启动", dir);
+                handle.restart();
+            }
+            Err(e) => {
+                log::error!("保存数据目录选择失败: {}", e);
+                prompt_for_data_dir(&handle, &dir, DATA_DIR_READONLY, &e);
+            }
+        }
+    });
+}
+
+// 运行期间解析出的各类目录
+pub struct AppPaths {
+    pub data_dir: PathBuf,
+    // 日志目录：backend.log、崩溃报告等
+    pub log_dir: PathBuf,
+    // 通过 switch_vault 切换到的文档库，None 表示默认文档库
+    active_vault: RwLock<Option<PathBuf>>,
+}
+
+// 解析日志目录：优先使用配置的目录，不可写时回退到数据目录下的 logs 子目录
+pub fn resolve_log_dir(data_dir: &Path, configured: Option<&Path>) -> PathBuf {
+    let default_dir = data_dir.join("logs");
+    if let Some(dir) = configured {
+        match std::fs::create_dir_all(dir).and_then(|_| probe_writable(dir)) {
+            Ok(_) => return dir.to_path_buf(),
+            Err(e) => log::warn!("日志目录 {:?} 不可写，回退到 {:?}: {}", dir, default_dir, e),
+        }
+    }
+    if let Err(e) = std::fs::create_dir_all(&default_dir).and_then(|_| probe_writable(&default_dir))
+    {
+        log::warn!("日志目录 {:?} 不可写: {}", default_dir, e);
+    }
+    default_dir
+}
+
+impl AppPaths {
+    pub fn new(data_dir: PathBuf, log_dir: PathBuf, active_vault: Option<PathBuf>) -> Self {
+        Self {
+            data_dir,
+            log_dir,
+            active_vault: RwLock::new(active_vault),
+        }
+    }
+
+    // 默认文档库：后端在数据目录下的 data 子目录中保存数据库、上传文件、索引和 LLM 配置
+    pub fn default_vault_dir(&self) -> PathBuf {
+        self.data_dir.join("data")
+    }
+
+    // 当前使用的文档库目录
+    pub fn vault_dir(&self) -> PathBuf {
+        self.active_vault
+            .read()
+            .unwrap()
+            .clone()
+            .unwrap_or_else(|| self.default_vault_dir())
+    }
+
+    // 切换到的文档库（使用默认文档库时为 None）
+    pub fn active_vault(&self) -> Option<PathBuf> {
+        self.active_vault.read().unwrap().clone()
+    }
+
+    pub fn set_active_vault(&self, vault: Option<PathBuf>) {
+        *self.active_vault.write().unwrap() = vault;
+    }
+
+    // 向量索引（ChromaDB）目录
+    pub fn index_dir(&self) -> PathBuf {
+        self.vault_dir().join("chroma")
+    }
+}
+
+// 递归统计目录占用的字节数，无法读取的条目忽略
+pub fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 在系统临时目录下创建一个本测试专用的空目录
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("document-qa-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn probe_writable_accepts_writable_dir() {
+        let dir = temp_dir("probe-writable");
+        probe_writable(&dir).unwrap();
+        // 探测文件用完即删
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn probe_writable_rejects_missing_dir() {
+        let dir = temp_dir("probe-missing").join("missing");
+        let error = probe_writable(&dir).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        std::fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn probe_writable_rejects_readonly_dir() {
+        let dir = temp_dir("probe-readonly");
+        let mut permissions = std::fs::metadata(&dir).unwrap().permissions();
+        permissions.set_readonly(true);
+        std::fs::set_permissions(&dir, permissions.clone()).unwrap();
+
+        // root 等特权用户不受目录权限限制（Windows 的只读属性也不阻止在目录中创建文件），
+        // 这时无法构造只读目录
+        let privileged = std::fs::write(dir.join("check"), b"").is_ok();
+        let result = probe_writable(&dir);
+
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+        std::fs::set_permissions(&dir, permissions).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        if privileged {
+            eprintln!("无法构造只读目录，跳过测试");
+            return;
+        }
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+    }
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use tauri::State;
+
+use crate::config::ConfigState;
+
+// top_k 允许 1 ~ 100，temperature 允许 0 ~ 2
+const MAX_TOP_K: u32 = 100;
+const MAX_TEMPERATURE: f32 = 2.0;
+const MAX_PRESET_NAME_LEN: usize = 64;
+
+// 一组命名的提问参数，未设置的参数使用后端默认值
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueryParams {
+    pub top_k: Option<u32>,
+    pub temperature: Option<f32>,
+}
+
+impl QueryParams {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(top_k) = self.top_k {
+            if !(1..=MAX_TOP_K).contains(&top_k) {
+                return Err(format!("top_k 必须在 1 ~ {} 之间", MAX_TOP_K));
+            }
+        }
+        if let Some(temperature) = self.temperature {
+            if !(0.0..=MAX_TEMPERATURE).contains(&temperature) {
+                return Err(format!("temperature 必须在 0 ~ {} 之间", MAX_TEMPERATURE));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.chars().count() > MAX_PRESET_NAME_LEN {
+        return Err(format!(
+            "预设名称长度必须在 1 ~ {} 之间",
+            MAX_PRESET_NAME_LEN
+        ));
+    }
+    Ok(())
+}
+
+// 把预设参数合并到发往后端的请求体中，预设不存在时返回错误
+pub fn apply_preset(
+    config: &ConfigState,
+    preset: Option<&str>,
+    body: &mut Value,
+) -> Result<(), String> {
+    let Some(name) = preset else {
+        return Ok(());
+    };
+    let presets = config.get().presets;
+    let params = presets
+        .get(name)
+        .ok_or_else(|| format!("提问预设不存在: {}", name))?;
+    if let (Value::Object(body), Ok(Value::Object(params))) = (body, serde_json::to_value(params)) {
+        body.extend(params.into_iter().filter(|(_, value)| !value.is_null()));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_presets(config: State<'_, ConfigState>) -> BTreeMap<String, QueryParams> {
+    config.get().presets
+}
+
+// 保存（或覆盖）一个提问预设
+#[tauri::command]
+pub fn save_preset(
+    config: State<'_, ConfigState>,
+    name: String,
+    params: QueryParams,
+) -> Result<(), String> {
+    let name = name.trim().to_string();
+    validate_name(&name)?;
+    params.validate()?;
+    let mut next = config.get();
+    next.presets.insert(name.clone(), params);
+    config.set(next)?;
+    log::info!("提问预设已保存: {}", name);
+    Ok(())
+}
+
+// 删除一个提问预设，返回该预设是否存在
+#[tauri::command]
+pub fn delete_preset(config: State<'_, ConfigState>, name: String) -> Result<bool, String> {
+    let mut next = config.get();
+    if next.presets.remove(&name).is_none() {
+        return Ok(false);
+    }
+    config.set(next)?;
+    log::info!("提问预设已删除: {}", name);
+    Ok(true)
+}
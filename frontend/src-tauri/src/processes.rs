@@ -0,0 +1,100 @@
+use serde::Serialize;
+use std::ffi::OsStr;
+use sysinfo::{Pid, Process, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+use tauri::State;
+
+use crate::backend::BackendProcess;
+
+// 系统中一个 Document-QA 后端进程
+#[derive(Debug, Serialize)]
+pub struct BackendProcessInfo {
+    pub pid: u32,
+    pub memory_mb: u64,
+    pub exe: Option<String>,
+    pub cmd: Vec<String>,
+    // 进程启动时间（Unix 时间戳，秒）
+    pub start_time: u64,
+    // 是否是本应用当前管理的后端
+    pub managed: bool,
+}
+
+fn refresh(system: &mut System, processes: ProcessesToUpdate) {
+    system.refresh_processes_specifics(
+        processes,
+        true,
+        ProcessRefreshKind::nothing()
+            .with_memory()
+            .with_exe(UpdateKind::OnlyIfNotSet)
+            .with_cmd(UpdateKind::OnlyIfNotSet),
+    );
+}
+
+// 判断进程是否是我们的后端：可执行文件名为 backend（打包后可能带目标三元组后缀），
+// 并且带有应用启动后端时总会传入的 --port 和 --data-dir 参数
+pub fn is_backend(process: &Process) -> bool {
+    let name = process
+        .exe()
+        .and_then(|exe| exe.file_stem())
+        .unwrap_or_else(|| process.name())
+        .to_string_lossy()
+        .to_string();
+    let name = name.strip_suffix(".exe").unwrap_or(&name);
+    if name != "backend" && !name.starts_with("backend-") {
+        return false;
+    }
+    let has_arg = |flag: &str| {
+        process.cmd().iter().any(|arg| {
+            arg == OsStr::new(flag) || arg.to_string_lossy().starts_with(&format!("{}=", flag))
+        })
+    };
+    has_arg("--port") && has_arg("--data-dir")
+}
+
+// 列出系统中所有的 Document-QA 后端进程（包括其他安装或崩溃后遗留的进程）
+#[tauri::command]
+pub fn list_backend_processes(state: State<'_, BackendProcess>) -> Vec<BackendProcessInfo> {
+    let managed = state.pid();
+    let mut system = System::new();
+    refresh(&mut system, ProcessesToUpdate::All);
+    let mut processes: Vec<BackendProcessInfo> = system
+        .processes()
+        .values()
+        .filter(|process| is_backend(process))
+        .map(|process| BackendProcessInfo {
+            pid: process.pid().as_u32(),
+            memory_mb: process.memory() / 1024 / 1024,
+            exe: process.exe().map(|exe| exe.to_string_lossy().to_string()),
+            cmd: process
+                .cmd()
+                .iter()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect(),
+            start_time: process.start_time(),
+            managed: Some(process.pid().as_u32()) == managed,
+        })
+        .collect();
+    processes.sort_by_key(|process| process.pid);
+    processes
+}
+
+// 结束一个遗留的后端进程；只允许结束确认是我们后端的进程，当前管理的后端请通过重启处理
+#[tauri::command]
+pub fn kill_backend_process(state: State<'_, BackendProcess>, pid: u32) -> Result<(), String> {
+    if state.pid() == Some(pid) {
+        return Err("该进程是当前正在使用的后端，请通过重启后端来处理".to_string());
+    }
+    let target = Pid::from_u32(pid);
+    let mut system = System::new();
+    refresh(&mut system, ProcessesToUpdate::Some(&[target]));
+    let process = system
+        .process(target)
+        .ok_or_else(|| format!("进程 {} 不存在", pid))?;
+    if !is_backend(process) {
+        return Err(format!("进程 {} 不是 Document-QA 后端，拒绝结束", pid));
+    }
+    if !process.kill() {
+        return Err(format!("结束进程 {} 失败", pid));
+    }
+    log::info!("已结束遗留的后端进程 (pid {})", pid);
+    Ok(())
+}
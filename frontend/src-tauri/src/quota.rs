@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::config::ConfigState;
+use crate::paths::{dir_size, AppPaths};
+
+// 文档库空间不足时错误信息的前缀，前端据此提示用户清理或调高上限
+pub const VAULT_FULL: &str = "VaultFull";
+// 文档库占用空间的缓存时间，批量导入时不必每个文件都重新遍历目录
+const USAGE_CACHE_TTL: Duration = Duration::from_secs(10);
+
+struct CachedUsage {
+    vault: PathBuf,
+    at: Instant,
+    bytes: u64,
+}
+
+// 文档库大小上限（max_vault_bytes）的检查，实际占用按磁盘上的文件大小统计
+#[derive(Default)]
+pub struct VaultQuota(Mutex<Option<CachedUsage>>);
+
+impl VaultQuota {
+    // 当前文档库占用的字节数，缓存未过期时直接返回
+    pub async fn usage(&self, vault: PathBuf) -> u64 {
+        if let Some(cached) = self.0.lock().unwrap().as_ref() {
+            if cached.vault == vault && cached.at.elapsed() < USAGE_CACHE_TTL {
+                return cached.bytes;
+            }
+        }
+        let walked = vault.clone();
+        let bytes = tauri::async_runtime::spawn_blocking(move || dir_size(&walked))
+            .await
+            .unwrap_or_default();
+        *self.0.lock().unwrap() = Some(CachedUsage {
+            vault,
+            at: Instant::now(),
+            bytes,
+        });
+        bytes
+    }
+
+    // 文件已写入文档库：计入缓存的占用，避免缓存期内连续导入超出上限
+    pub fn add(&self, bytes: u64) {
+        if let Some(cached) = self.0.lock().unwrap().as_mut() {
+            cached.bytes += bytes;
+        }
+    }
+}
+
+// 检查再写入 incoming 字节后文档库是否会超出上限；未设置上限时总是通过
+pub async fn check(app: &AppHandle, incoming: u64) -> Result<(), String> {
+    let Some(max) = app.state::<ConfigState>().get().max_vault_bytes else {
+        return Ok(());
+    };
+    let vault = app.state::<AppPaths>().vault_dir();
+    let used = app.state::<VaultQuota>().usage(vault).await;
+    if used.saturating_add(incoming) > max {
+        return Err(format!(
+            "{}: 文档库空间不足（已用 {} 字节，还需 {} 字节，上限 {} 字节）",
+            VAULT_FULL, used, incoming, max
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::State;
+
+use crate::backend::now_millis;
+
+// 数据目录中的最近文档记录文件
+const RECENT_FILE: &str = "recent.json";
+// 最多保留的最近文档条数
+const MAX_RECENT: usize = 20;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecentAction {
+    Opened,
+    Ingested,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentDocument {
+    // 文档 ID：后端文档 ID，没有时使用本地路径
+    pub id: String,
+    pub name: String,
+    // 本地文件路径（如果有）
+    pub path: Option<PathBuf>,
+    pub action: RecentAction,
+    pub timestamp_ms: u64,
+}
+
+// 跨会话保存的最近文档列表（最新的在前）
+pub struct RecentDocuments {
+    path: PathBuf,
+    entries: Mutex<Vec<RecentDocument>>,
+}
+
+impl RecentDocuments {
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(RECENT_FILE);
+        let entries = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                log::warn!("最近文档记录解析失败，已忽略: {}", e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self {
+            path,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    // 记录一次打开或导入：同一 ID 的旧记录移到最前面，超出上限的丢弃
+    pub fn record(&self, id: String, name: String, path: Option<PathBuf>, action: RecentAction) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|entry| entry.id != id);
+        entries.insert(
+            0,
+            RecentDocument {
+                id,
+                name,
+                path,
+                action,
+                timestamp_ms: now_millis(),
+            },
+        );
+        entries.truncate(MAX_RECENT);
+        if let Err(e) = self.save(&entries) {
+            log::warn!("{}", e);
+        }
+    }
+
+    // 返回最近文档，顺带清理本地文件已被删除的记录
+    pub fn list(&self) -> Vec<RecentDocument> {
+        let mut entries = self.entries.lock().unwrap();
+        let before = entries.len();
+        entries.retain(|entry| entry.path.as_ref().map_or(true, |path| path.exists()));
+        if entries.len() != before {
+            log::info!(
+                "已清理 {} 条指向已删除文件的最近文档",
+                before - entries.len()
+            );
+            if let Err(e) = self.save(&entries) {
+                log::warn!("{}", e);
+            }
+        }
+        entries.clone()
+    }
+
+    pub fn clear(&self) -> Result<(), String> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.clear();
+        self.save(&entries)
+    }
+
+    // 先写临时文件再重命名，避免写入中途崩溃留下半个文件
+    fn save(&self, entries: &[RecentDocument]) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, text).map_err(|e| format!("写入最近文档记录失败: {}", e))?;
+        std::fs::rename(&tmp, &self.path).map_err(|e| format!("保存最近文档记录失败: {}", e))
+    }
+}
+
+#[tauri::command]
+pub fn get_recent_documents(recent: State<'_, RecentDocuments>) -> Vec<RecentDocument> {
+    recent.list()
+}
+
+#[tauri::command]
+pub fn clear_recent_documents(recent: State<'_, RecentDocuments>) -> Result<(), String> {
+    recent.clear()
+}
@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::{self, now_millis};
+use crate::logs::BackendLogs;
+use crate::paths::AppPaths;
+
+// 记录上次重新启动时间的文件（位于数据目录下）
+const RELAUNCH_MARKER: &str = ".last-relaunch";
+// 两次重新启动之间的最短间隔，防止启动后立刻再次触发形成循环
+const MIN_RELAUNCH_INTERVAL: Duration = Duration::from_secs(60);
+// 等待后端退出的最长时间
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+// 防止重新启动被重复触发
+pub struct RelaunchGuard {
+    running: AtomicBool,
+}
+
+impl RelaunchGuard {
+    pub fn new() -> Self {
+        Self {
+            running: AtomicBool::new(false),
+        }
+    }
+}
+
+fn marker_path(app: &AppHandle) -> PathBuf {
+    app.state::<AppPaths>().data_dir.join(RELAUNCH_MARKER)
+}
+
+// 上次重新启动距今的时间；没有记录（或无法读取）时返回 None
+fn since_last_relaunch(app: &AppHandle) -> Option<Duration> {
+    let text = std::fs::read_to_string(marker_path(app)).ok()?;
+    let last: u64 = text.trim().parse().ok()?;
+    Some(Duration::from_millis(now_millis().saturating_sub(last)))
+}
+
+// 完整地重新启动应用：先关闭后端并等待其退出、端口释放，再重新执行当前程序
+#[tauri::command]
+pub async fn relaunch(app: AppHandle, guard: State<'_, RelaunchGuard>) -> Result<(), String> {
+    if let Some(elapsed) = since_last_relaunch(&app) {
+        if elapsed < MIN_RELAUNCH_INTERVAL {
+            return Err(format!(
+                "应用 {} 秒前刚重新启动过，请稍后再试",
+                elapsed.as_secs()
+            ));
+        }
+    }
+    if guard.running.swap(true, Ordering::SeqCst) {
+        return Err("应用正在重新启动".to_string());
+    }
+
+    log::info!("正在重新启动应用...");
+    if !backend::stop_backend_and_wait(&app, SHUTDOWN_TIMEOUT).await {
+        guard.running.store(false, Ordering::SeqCst);
+        return Err("后端未能在规定时间内退出，已取消重新启动".to_string());
+    }
+    if let Err(e) = std::fs::write(marker_path(&app), now_millis().to_string()) {
+        log::warn!("写入重新启动记录失败: {}", e);
+    }
+    app.state::<BackendLogs>().flush_session();
+    app.restart()
+}
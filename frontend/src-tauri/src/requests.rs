@@ -0,0 +1,381 @@
+use futures_util::future::{AbortHandle, AbortRegistration, Abortable};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::backend::now_millis;
+use crate::config::{AppConfig, ConfigState, DEFAULT_MAX_CONCURRENT_REQUESTS};
+use crate::http::{self, BackendClient};
+use crate::import::IngestQueue;
+use crate::logs::{self, BackendLogs, LogLine};
+
+// 并发请求数达到上限且未开启排队时返回的错误前缀，前端据此区分
+pub const TOO_MANY_REQUESTS: &str = "TooManyRequests";
+// 每个请求都带上关联 ID，后端在响应中原样返回
+const REQUEST_ID_HEADER: &str = "X-Request-Id";
+// 开启请求追踪时附带，后端据此输出带关联 ID 的处理日志
+const REQUEST_TRACE_HEADER: &str = "X-Request-Trace";
+
+// 限制同时发往后端的请求数量，保护资源有限的后端
+pub struct RequestLimiter(Mutex<LimiterSettings>);
+
+// 修改配置时整体替换；进行中的请求在旧的信号量上归还名额，不受影响
+#[derive(Clone)]
+struct LimiterSettings {
+    semaphore: Arc<Semaphore>,
+    limit: usize,
+    queue: bool,
+    timeout: Duration,
+}
+
+impl LimiterSettings {
+    fn new(config: &AppConfig) -> Self {
+        let limit = if config.max_concurrent_requests == 0 {
+            log::warn!("max_concurrent_requests 不能为 0，使用默认值");
+            DEFAULT_MAX_CONCURRENT_REQUESTS
+        } else {
+            config.max_concurrent_requests
+        };
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit)),
+            limit,
+            queue: config.queue_when_saturated,
+            timeout: Duration::from_secs(config.queue_timeout_secs),
+        }
+    }
+}
+
+impl RequestLimiter {
+    pub fn new(config: &AppConfig) -> Self {
+        Self(Mutex::new(LimiterSettings::new(config)))
+    }
+
+    fn settings(&self) -> LimiterSettings {
+        self.0.lock().unwrap().clone()
+    }
+
+    // 按新的配置调整并发上限和排队方式，立即生效
+    pub fn reconfigure(&self, config: &AppConfig) {
+        let mut settings = self.0.lock().unwrap();
+        if settings.limit == config.max_concurrent_requests
+            && settings.queue == config.queue_when_saturated
+            && settings.timeout.as_secs() == config.queue_timeout_secs
+        {
+            return;
+        }
+        *settings = LimiterSettings::new(config);
+        log::info!("并发请求上限已更新为 {}", settings.limit);
+    }
+
+    // 获取一个请求名额，请求结束时释放 permit 即归还
+    pub async fn acquire(&self) -> Result<OwnedSemaphorePermit, String> {
+        let settings = self.settings();
+        if !settings.queue {
+            return settings.semaphore.try_acquire_owned().map_err(|_| {
+                format!(
+                    "{}: 同时进行的请求过多（上限 {}）",
+                    TOO_MANY_REQUESTS, settings.limit
+                )
+            });
+        }
+        match tokio::time::timeout(settings.timeout, settings.semaphore.acquire_owned()).await {
+            Ok(Ok(permit)) => Ok(permit),
+            Ok(Err(_)) => Err("请求限流器已关闭".to_string()),
+            Err(_) => Err(format!(
+                "{}: 排队等待超过 {} 秒",
+                TOO_MANY_REQUESTS,
+                settings.timeout.as_secs()
+            )),
+        }
+    }
+
+    // 临时占用除一个以外的全部名额，把并发降为 1；释放返回的 permit 即恢复。
+    // 名额正被占用时返回 None，调用方稍后重试
+    pub fn throttle(&self) -> Option<OwnedSemaphorePermit> {
+        let settings = self.settings();
+        let reserved = u32::try_from(settings.limit - 1).ok().filter(|n| *n > 0)?;
+        settings.semaphore.try_acquire_many_owned(reserved).ok()
+    }
+
+    fn in_flight(&self) -> (usize, usize) {
+        let settings = self.settings();
+        (
+            settings.limit - settings.semaphore.available_permits(),
+            settings.limit,
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct InFlightRequests {
+    pub in_flight: usize,
+    pub limit: usize,
+}
+
+// 当前进行中的请求数，用于界面上的负载指示
+#[tauri::command]
+pub fn get_in_flight_requests(limiter: State<'_, RequestLimiter>) -> InFlightRequests {
+    let (in_flight, limit) = limiter.in_flight();
+    InFlightRequests { in_flight, limit }
+}
+
+// 请求追踪：为每个后端请求生成关联 ID；开启时记录请求的发送、响应状态和耗时。
+// 只在本次运行内有效，默认关闭
+pub struct RequestTracing {
+    enabled: AtomicBool,
+    next_id: AtomicU64,
+}
+
+impl RequestTracing {
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    // 关联 ID：当前时间戳加序号，在一次运行内唯一，也便于在日志中搜索
+    fn correlation_id(&self) -> String {
+        format!(
+            "{:x}-{}",
+            now_millis(),
+            self.next_id.fetch_add(1, Ordering::Relaxed)
+        )
+    }
+}
+
+// 开启或关闭请求追踪
+#[tauri::command]
+pub fn set_request_tracing(tracing: State<'_, RequestTracing>, enabled: bool) {
+    tracing.enabled.store(enabled, Ordering::SeqCst);
+    log::info!("请求追踪已{}", if enabled { "开启" } else { "关闭" });
+}
+
+// 返回与指定关联 ID 有关的日志：应用记录的请求过程和后端输出的处理日志
+#[tauri::command]
+pub fn get_request_trace(logs: State<'_, BackendLogs>, correlation_id: String) -> Vec<LogLine> {
+    if correlation_id.is_empty() {
+        return Vec::new();
+    }
+    logs.matching(&correlation_id)
+}
+
+// 正在进行中的后端请求，按前端传入的 request_id 索引，用于取消
+pub struct PendingRequests {
+    handles: Mutex<HashMap<String, AbortHandle>>,
+    // 前端未提供 request_id 时用于生成内部 id
+    next_id: AtomicU64,
+}
+
+impl PendingRequests {
+    pub fn new() -> Self {
+        Self {
+            handles: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    // 为调用方没有指定 ID 的请求生成一个内部 ID
+    pub fn generate_id(&self) -> String {
+        format!("internal-{}", self.next_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    // 登记一个请求，返回用于包装请求 future 的注册句柄
+    pub fn register(&self, request_id: &str) -> Result<AbortRegistration, String> {
+        let mut map = self.handles.lock().unwrap();
+        if map.contains_key(request_id) {
+            return Err(format!("请求 {} 已在进行中", request_id));
+        }
+        let (handle, registration) = AbortHandle::new_pair();
+        map.insert(request_id.to_string(), handle);
+        Ok(registration)
+    }
+
+    // 请求结束（无论成功、失败还是被取消）后移除登记
+    pub fn finish(&self, request_id: &str) {
+        self.handles.lock().unwrap().remove(request_id);
+    }
+
+    pub fn cancel(&self, request_id: &str) -> bool {
+        match self.handles.lock().unwrap().remove(request_id) {
+            Some(handle) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    // 取消全部请求。先在锁内取出全部句柄再逐个中止，
+    // 不会在持锁期间做其他操作，可以与 register 并发调用
+    pub fn cancel_all(&self) -> usize {
+        let handles = std::mem::take(&mut *self.handles.lock().unwrap());
+        for handle in handles.values() {
+            handle.abort();
+        }
+        handles.len()
+    }
+}
+
+// 取消指定的请求，返回是否找到了该请求
+#[tauri::command]
+pub fn cancel_request(pending: State<'_, PendingRequests>, request_id: String) -> bool {
+    let cancelled = pending.cancel(&request_id);
+    if cancelled {
+        log::info!("已取消请求 {}", request_id);
+    }
+    cancelled
+}
+
+// 取消全部进行中的请求（可重复调用），并清空导入队列中尚未上传的文件，
+// 完成后发送 all-cancelled 事件。返回取消的请求数
+#[tauri::command]
+pub fn cancel_all(
+    app: AppHandle,
+    pending: State<'_, PendingRequests>,
+    queue: State<'_, IngestQueue>,
+) -> usize {
+    let count = pending.cancel_all();
+    let cleared = queue.clear();
+    log::info!(
+        "已取消全部请求，共 {} 个；清空导入队列中的 {} 个文件",
+        count,
+        cleared
+    );
+    let _ = app.emit(
+        "all-cancelled",
+        serde_json::json!({ "count": count, "cleared_files": cleared }),
+    );
+    count
+}
+
+// 通用的后端请求：转发到后端 API 并返回 JSON 结果。
+// 传入 request_id 时可以通过 cancel_request 取消。
+// idempotent 控制连接失败时是否重试，未传入时 GET/HEAD/PUT/DELETE/OPTIONS 视为幂等，
+// POST（如上传摄取文档）默认不重试。开启 log_http 时把每次请求记录到日志文件。
+// 每个请求带有 X-Request-Id 关联 ID，开启请求追踪时按该 ID 记录请求的完整过程
+#[tauri::command]
+pub async fn backend_request(
+    app: AppHandle,
+    limiter: State<'_, RequestLimiter>,
+    method: String,
+    path: String,
+    body: Option<Value>,
+    request_id: Option<String>,
+    idempotent: Option<bool>,
+) -> Result<Value, String> {
+    let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
+        .map_err(|_| format!("无效的请求方法: {}", method))?;
+    if !path.starts_with('/') {
+        return Err(format!("请求路径必须以 / 开头: {}", path));
+    }
+
+    let idempotent = idempotent.unwrap_or(method.is_idempotent());
+    let client = app.state::<BackendClient>();
+    let pending = app.state::<PendingRequests>();
+    let request_id = request_id.unwrap_or_else(|| pending.generate_id());
+    let registration = pending.register(&request_id)?;
+
+    let log_http = app.state::<ConfigState>().get().log_http;
+    let tracing = app.state::<RequestTracing>();
+    let traced = tracing.enabled();
+    let correlation_id = tracing.correlation_id();
+    let mut request = client
+        .request(method.clone(), &path)
+        .header(REQUEST_ID_HEADER, &correlation_id);
+    if traced {
+        request = request.header(REQUEST_TRACE_HEADER, "1");
+        logs::record(
+            &app,
+            "http",
+            "info",
+            format!("[req:{}] 发送 {} {}", correlation_id, method, path),
+        );
+    }
+    if let Some(body) = &body {
+        request = request.json(body);
+    }
+    let started = Instant::now();
+    // 先登记再排队等待名额，排队中的请求同样可以被取消
+    let send = async {
+        let _permit = limiter.acquire().await?;
+        let response = http::send(request, idempotent)
+            .await
+            .map_err(|e| format!("连接后端失败: {}", e))?;
+        let status = response.status();
+        if traced {
+            logs::record(
+                &app,
+                "http",
+                "info",
+                format!(
+                    "[req:{}] 响应 {} ({} ms)",
+                    correlation_id,
+                    status.as_u16(),
+                    started.elapsed().as_millis()
+                ),
+            );
+        }
+        let value = if status == reqwest::StatusCode::NO_CONTENT {
+            Value::Null
+        } else {
+            response
+                .json::<Value>()
+                .await
+                .map_err(|e| format!("解析后端响应失败: {}", e))?
+        };
+        if log_http {
+            logs::record(
+                &app,
+                "http",
+                "info",
+                format!(
+                    "[req:{}] {} {} -> {} ({} ms) 请求: {} 响应: {}",
+                    correlation_id,
+                    method,
+                    path,
+                    status.as_u16(),
+                    started.elapsed().as_millis(),
+                    body.as_ref().map(logs::summarize_body).unwrap_or_default(),
+                    logs::summarize_body(&value)
+                ),
+            );
+        }
+        if status.is_success() {
+            Ok(value)
+        } else {
+            Err(format!("后端返回错误状态 {}: {}", status, value))
+        }
+    };
+
+    let result = Abortable::new(send, registration).await;
+    pending.finish(&request_id);
+    let result = result.unwrap_or_else(|_| Err(format!("请求 {} 已取消", request_id)));
+    if log_http || traced {
+        if let Err(message) = &result {
+            logs::record(
+                &app,
+                "http",
+                "warn",
+                format!(
+                    "[req:{}] {} {} 失败 ({} ms): {}",
+                    correlation_id,
+                    method,
+                    path,
+                    started.elapsed().as_millis(),
+                    message
+                ),
+            );
+        }
+    }
+    result
+}
@@ -0,0 +1,103 @@
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::backend::{self, BackendProcess};
+use crate::http::{check_health, BackendClient};
+
+// 检测间隔
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+// 墙上时间比单调时钟多走了超过该值，就认为系统经历了休眠
+const RESUME_THRESHOLD: Duration = Duration::from_secs(30);
+
+// 休眠期间单调时钟（Instant）基本不前进，而墙上时间照常前进，
+// 两者的差值超过 threshold 即判定为刚从休眠中恢复。时钟被调回过去时不算
+fn is_resume(wall_elapsed: Duration, mono_elapsed: Duration, threshold: Duration) -> bool {
+    wall_elapsed.saturating_sub(mono_elapsed) > threshold
+}
+
+// 监控系统休眠/唤醒，唤醒后重新检查后端健康状态，
+// 后端已在休眠期间退出时走正常的重启流程
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut last_wall = SystemTime::now();
+        let mut last_mono = Instant::now();
+        loop {
+            tokio::time::sleep(TICK_INTERVAL).await;
+            if app.state::<BackendProcess>().shutting_down() {
+                break;
+            }
+
+            let wall_elapsed = last_wall.elapsed().unwrap_or_default();
+            let mono_elapsed = last_mono.elapsed();
+            last_wall = SystemTime::now();
+            last_mono = Instant::now();
+            if !is_resume(wall_elapsed, mono_elapsed, RESUME_THRESHOLD) {
+                continue;
+            }
+
+            log::info!(
+                "检测到系统从休眠中恢复（约 {} 秒），检查后端状态",
+                wall_elapsed.as_secs()
+            );
+            let client = app.state::<BackendClient>();
+            let healthy = check_health(&client).await;
+            if !healthy && app.state::<BackendProcess>().pid().is_none() {
+                log::warn!("后端已在休眠期间退出，正在重启");
+                if let Err(e) = backend::restart_backend(&app).await {
+                    log::error!("唤醒后重启后端失败: {}", e);
+                }
+            }
+            // 休眠前的流式连接大多已失效，前端收到事件后应重新发起
+            let _ = app.emit("backend-resumed", serde_json::json!({ "healthy": healthy }));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: Duration = Duration::from_secs(30);
+
+    #[test]
+    fn normal_tick_is_not_resume() {
+        let tick = Duration::from_secs(5);
+        assert!(!is_resume(tick, tick, THRESHOLD));
+        // 调度延迟造成的少量偏差不算
+        assert!(!is_resume(
+            tick + Duration::from_millis(300),
+            tick,
+            THRESHOLD
+        ));
+    }
+
+    #[test]
+    fn gap_at_threshold_is_not_resume() {
+        let mono = Duration::from_secs(5);
+        assert!(!is_resume(mono + THRESHOLD, mono, THRESHOLD));
+    }
+
+    #[test]
+    fn gap_over_threshold_is_resume() {
+        let mono = Duration::from_secs(5);
+        let wall = mono + THRESHOLD + Duration::from_millis(1);
+        assert!(is_resume(wall, mono, THRESHOLD));
+        assert!(is_resume(Duration::from_secs(3600), mono, THRESHOLD));
+    }
+
+    #[test]
+    fn clock_set_backwards_is_not_resume() {
+        // 墙上时间被调回过去时 elapsed() 失败，调用方按 0 处理
+        assert!(!is_resume(
+            Duration::ZERO,
+            Duration::from_secs(5),
+            THRESHOLD
+        ));
+        assert!(!is_resume(
+            Duration::from_secs(1),
+            Duration::from_secs(60),
+            THRESHOLD
+        ));
+    }
+}
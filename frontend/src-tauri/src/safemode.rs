@@ -0,0 +1,37 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::backend;
+
+// 以安全模式启动应用的命令行参数
+const SAFE_MODE_FLAG: &str = "--safe-mode";
+
+// 安全模式：后端不自动为上传的文档建立索引，便于找出并删除导致崩溃的文档。
+// 只在本次运行内有效，正常重新启动应用即恢复
+pub struct SafeMode(AtomicBool);
+
+impl SafeMode {
+    // 根据命令行参数决定是否以安全模式启动
+    pub fn from_args() -> Self {
+        let active = std::env::args().any(|arg| arg == SAFE_MODE_FLAG);
+        if active {
+            log::warn!("以安全模式启动");
+        }
+        Self(AtomicBool::new(active))
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+// 切换到安全模式并重启后端；重新启动应用后恢复正常模式
+#[tauri::command]
+pub async fn start_safe_mode(app: AppHandle, safe_mode: State<'_, SafeMode>) -> Result<(), String> {
+    if safe_mode.0.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    log::warn!("进入安全模式，正在重启后端");
+    let _ = app.emit("safe-mode-changed", true);
+    backend::restart_backend(&app).await
+}
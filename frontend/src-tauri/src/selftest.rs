@@ -0,0 +1,247 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, State};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+use tokio::sync::Mutex;
+
+use crate::backend::{self, now_millis};
+use crate::http::{self, check_health, BackendClient, BACKEND_PORT};
+use crate::import::upload_document;
+use crate::sidecar;
+use crate::vault;
+
+// 自检用的临时后端监听的端口，避免与正在运行的后端冲突
+const SELF_TEST_PORT: u16 = BACKEND_PORT + 1;
+const READY_TIMEOUT: Duration = Duration::from_secs(120);
+const INGEST_TIMEOUT: Duration = Duration::from_secs(120);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// 自检使用的示例文档、提问和回答中应包含的内容
+const SAMPLE_FILE_NAME: &str = "self-test.txt";
+const SAMPLE_DOCUMENT: &str =
+    "Document Q&A 自检文档。\n\n灯塔计划的项目代号是 AURORA-7，由北港研究所负责。\n";
+const SAMPLE_QUESTION: &str = "灯塔计划的项目代号是什么？";
+const EXPECTED_ANSWER: &str = "AURORA-7";
+
+// 防止同时运行多个自检
+pub struct SelfTest {
+    running: Mutex<()>,
+}
+
+impl SelfTest {
+    pub fn new() -> Self {
+        Self {
+            running: Mutex::new(()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SelfTestStep {
+    pub name: &'static str,
+    pub passed: bool,
+    pub duration_ms: u64,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub steps: Vec<SelfTestStep>,
+}
+
+impl SelfTestReport {
+    // 记录一个步骤的结果，返回该步骤是否通过
+    fn record<T>(
+        &mut self,
+        name: &'static str,
+        started: Instant,
+        result: &Result<T, String>,
+    ) -> bool {
+        let step = SelfTestStep {
+            name,
+            passed: result.is_ok(),
+            duration_ms: started.elapsed().as_millis() as u64,
+            detail: result.as_ref().err().cloned(),
+        };
+        log::info!(
+            "自检步骤 {}: {}",
+            name,
+            if step.passed { "通过" } else { "失败" }
+        );
+        self.passed &= step.passed;
+        self.steps.push(step);
+        self.passed
+    }
+}
+
+// 用临时数据目录启动一个独立的后端实例，数据库、向量库和上传目录都指向该目录
+async fn spawn_test_backend(app: &AppHandle, vault: &Path) -> Result<CommandChild, String> {
+    sidecar::ensure_integrity(app).await?;
+    let plan = backend::spawn_plan(app)?;
+    let vault_str = vault.to_string_lossy().to_string();
+    let mut env: Vec<(String, String)> = plan
+        .env
+        .iter()
+        .filter(|(key, _)| key != "HOST" && key != "PORT")
+        .cloned()
+        .collect();
+    env.extend([
+        ("HOST".to_string(), "127.0.0.1".to_string()),
+        ("PORT".to_string(), SELF_TEST_PORT.to_string()),
+    ]);
+    env.extend(vault::storage_env(vault));
+
+    let (mut rx, child) = backend::base_command(app, &plan)?
+        .args([
+            "--port",
+            &SELF_TEST_PORT.to_string(),
+            "--data-dir",
+            &vault_str,
+        ])
+        .current_dir(vault)
+        .envs(env)
+        .spawn()
+        .map_err(|e| format!("启动后端服务失败: {}", e))?;
+
+    // 持续读取输出，避免管道写满阻塞子进程
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stdout(line) | CommandEvent::Stderr(line) => {
+                    log::debug!("[SelfTest] {}", String::from_utf8_lossy(&line).trim_end());
+                }
+                CommandEvent::Terminated(_) => break,
+                _ => {}
+            }
+        }
+    });
+    Ok(child)
+}
+
+async fn wait_ready(client: &BackendClient) -> Result<(), String> {
+    let started = Instant::now();
+    while started.elapsed() < READY_TIMEOUT {
+        if check_health(client).await {
+            return Ok(());
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    Err(format!("后端在 {} 秒内未就绪", READY_TIMEOUT.as_secs()))
+}
+
+// 上传示例文档并等待后端处理完成
+async fn ingest_sample(client: &BackendClient) -> Result<(), String> {
+    let uploaded = upload_document(
+        client,
+        SAMPLE_FILE_NAME,
+        SAMPLE_DOCUMENT.as_bytes().to_vec(),
+    )
+    .await?;
+    let id = uploaded["id"].as_i64().ok_or("后端未返回文档 ID")?;
+
+    let started = Instant::now();
+    while started.elapsed() < INGEST_TIMEOUT {
+        let document: Value = http::send(client.get(&format!("/api/v1/documents/{}", id)), true)
+            .await
+            .map_err(|e| format!("连接后端失败: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("解析后端响应失败: {}", e))?;
+        match document["status"].as_str() {
+            Some("processed") => return Ok(()),
+            Some("failed") => return Err("后端处理示例文档失败".to_string()),
+            _ => tokio::time::sleep(POLL_INTERVAL).await,
+        }
+    }
+    Err(format!(
+        "示例文档在 {} 秒内未处理完成",
+        INGEST_TIMEOUT.as_secs()
+    ))
+}
+
+// 针对示例文档提问，检查回答中是否包含文档里的内容
+async fn query_sample(client: &BackendClient) -> Result<(), String> {
+    let response = http::send(
+        client
+            .post("/api/v1/chat")
+            .json(&json!({ "question": SAMPLE_QUESTION })),
+        true,
+    )
+    .await
+    .map_err(|e| format!("连接后端失败: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("后端返回错误状态: {}", response.status()));
+    }
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("解析后端响应失败: {}", e))?;
+    let answer = body["answer"].as_str().unwrap_or_default();
+    if !answer.contains(EXPECTED_ANSWER) {
+        return Err(format!("回答中没有找到 {}: {}", EXPECTED_ANSWER, answer));
+    }
+    Ok(())
+}
+
+// 端到端自检：用临时文档库启动后端、摄取示例文档并提问，最后清理临时数据。
+// 不会读写用户的文档库
+#[tauri::command]
+pub async fn run_self_test(
+    app: AppHandle,
+    self_test: State<'_, SelfTest>,
+) -> Result<SelfTestReport, String> {
+    let _running = self_test
+        .running
+        .try_lock()
+        .map_err(|_| "自检正在进行中".to_string())?;
+    let vault: PathBuf =
+        std::env::temp_dir().join(format!("document-qa-self-test-{}", now_millis()));
+    std::fs::create_dir_all(&vault).map_err(|e| format!("创建临时文档库失败: {}", e))?;
+    log::info!("开始自检，临时文档库 {:?}", vault);
+
+    let mut report = SelfTestReport {
+        passed: true,
+        steps: Vec::new(),
+    };
+    let client = BackendClient::with_port("127.0.0.1", SELF_TEST_PORT);
+
+    let started = Instant::now();
+    let child = spawn_test_backend(&app, &vault).await;
+    let child = match child {
+        Ok(child) => {
+            let ready = wait_ready(&client).await;
+            report.record("spawn", started, &ready);
+            Some(child)
+        }
+        Err(e) => {
+            report.record("spawn", started, &Err::<(), _>(e));
+            None
+        }
+    };
+
+    if report.passed {
+        let started = Instant::now();
+        let result = ingest_sample(&client).await;
+        report.record("ingest", started, &result);
+    }
+    if report.passed {
+        let started = Instant::now();
+        let result = query_sample(&client).await;
+        report.record("query", started, &result);
+    }
+
+    let started = Instant::now();
+    if let Some(child) = child {
+        let _ = child.kill();
+        // 等待进程释放数据库等文件后再删除临时目录
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    let cleanup = std::fs::remove_dir_all(&vault).map_err(|e| format!("删除临时文档库失败: {}", e));
+    report.record("cleanup", started, &cleanup);
+
+    log::info!("自检结束: {}", if report.passed { "通过" } else { "失败" });
+    Ok(report)
+}
@@ -0,0 +1,150 @@
+use serde::Serialize;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend;
+use crate::config::{AppConfig, ConfigState};
+use crate::integrity::SidecarIntegrity;
+use crate::update;
+
+// 运行 --version 的最长时间；不认识该参数的后端可能直接开始运行服务
+const VERSION_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+const VERSION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// 当前使用的后端可执行文件来源
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BackendSource {
+    // 随应用打包的后端
+    Bundled,
+    // 用户通过 custom_backend_path 指定的后端
+    Custom { path: PathBuf },
+}
+
+impl BackendSource {
+    // 后端可执行文件的完整路径
+    pub fn program_path(&self) -> Option<PathBuf> {
+        match self {
+            BackendSource::Bundled => update::sidecar_path(),
+            BackendSource::Custom { path } => Some(path.clone()),
+        }
+    }
+}
+
+pub fn source(config: &AppConfig) -> BackendSource {
+    match &config.custom_backend_path {
+        Some(path) => BackendSource::Custom { path: path.clone() },
+        None => BackendSource::Bundled,
+    }
+}
+
+// 启动前检查：打包的后端需要通过完整性校验，自定义后端由用户自行负责
+pub async fn ensure_integrity(app: &AppHandle) -> Result<(), String> {
+    match source(&app.state::<ConfigState>().get()) {
+        BackendSource::Bundled => app.state::<SidecarIntegrity>().ensure().await,
+        BackendSource::Custom { .. } => Ok(()),
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(_path: &Path, meta: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    meta.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path, _meta: &std::fs::Metadata) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+// 检查自定义后端：文件存在、是普通文件并且可以执行。返回规范化后的绝对路径
+pub fn validate(path: &Path) -> Result<PathBuf, String> {
+    let path = std::fs::canonicalize(path)
+        .map_err(|e| format!("自定义后端 {:?} 不存在或无法访问: {}", path, e))?;
+    let meta = std::fs::metadata(&path).map_err(|e| format!("读取 {:?} 失败: {}", path, e))?;
+    if !meta.is_file() {
+        return Err(format!("自定义后端 {:?} 不是文件", path));
+    }
+    if !is_executable(&path, &meta) {
+        return Err(format!("自定义后端 {:?} 不是可执行文件", path));
+    }
+    Ok(path)
+}
+
+// 运行 `<path> --version` 并返回输出（阻塞操作）。超时未退出时结束进程
+fn probe_version(path: &Path) -> Result<String, String> {
+    let mut child = Command::new(path)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("运行自定义后端失败: {}", e))?;
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() < VERSION_PROBE_TIMEOUT => {
+                std::thread::sleep(VERSION_POLL_INTERVAL)
+            }
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err("自定义后端不支持 --version（运行超时）".to_string());
+            }
+            Err(e) => return Err(format!("等待自定义后端退出失败: {}", e)),
+        }
+    };
+    let mut output = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        let _ = stdout.read_to_string(&mut output);
+    }
+    if !status.success() {
+        return Err(format!("自定义后端 --version 失败: {}", status));
+    }
+    Ok(output.trim().to_string())
+}
+
+// 设置自定义后端可执行文件（None 恢复使用打包的后端）。检查通过后才保存并重启后端；
+// 指定 expected_version 时还要求 --version 的输出包含该版本号
+#[tauri::command]
+pub async fn set_custom_backend(
+    app: AppHandle,
+    config: State<'_, ConfigState>,
+    path: Option<String>,
+    expected_version: Option<String>,
+) -> Result<BackendSource, String> {
+    let path = match path.filter(|path| !path.trim().is_empty()) {
+        Some(path) => {
+            let path = validate(Path::new(path.trim()))?;
+            if let Some(expected) = expected_version.filter(|v| !v.trim().is_empty()) {
+                let probed = path.clone();
+                let version = tauri::async_runtime::spawn_blocking(move || probe_version(&probed))
+                    .await
+                    .map_err(|e| format!("检查自定义后端版本失败: {}", e))??;
+                if !version.contains(expected.trim()) {
+                    return Err(format!(
+                        "自定义后端版本不匹配：期望 {}，实际输出 {:?}",
+                        expected.trim(),
+                        version
+                    ));
+                }
+                log::info!("自定义后端版本: {}", version);
+            }
+            Some(path)
+        }
+        None => None,
+    };
+
+    let mut next = config.get();
+    next.custom_backend_path = path;
+    let active = source(&next);
+    config.set(next)?;
+    log::info!("后端可执行文件来源已更新: {:?}", active);
+    backend::restart_backend(&app).await?;
+    Ok(active)
+}
@@ -0,0 +1,161 @@
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::backend::{self, now_millis, BackendProcess};
+use crate::capabilities;
+use crate::config::ConfigState;
+use crate::credentials;
+use crate::info;
+use crate::logs::{self, BackendLogs};
+use crate::paths::AppPaths;
+use crate::vault;
+
+// 诊断包中的说明文件
+const README: &str = "Document-QA 诊断包
+
+本压缩包用于排查问题，已隐去 API 密钥等凭据，不包含任何文档内容和索引数据。
+
+- logs/          后端日志（backend.log、session.log 及轮转的旧文件）和崩溃报告
+- config.json    应用配置（凭据字段已替换为 ***）
+- llm_config.json 后端的 LLM 配置（凭据字段已替换为 ***）
+- environment.json 应用版本、操作系统、平台能力、后端启动方案和最近一次后端失败
+- vault.json     当前文档库的位置、占用空间、缓存大小和文档数量
+";
+
+type Zip = ZipWriter<File>;
+
+fn options() -> SimpleFileOptions {
+    SimpleFileOptions::default().compression_method(CompressionMethod::Deflated)
+}
+
+fn add_json(zip: &mut Zip, name: &str, mut value: Value) -> Result<(), String> {
+    logs::redact_json(&mut value);
+    let text = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
+    zip.start_file(name, options()).map_err(|e| e.to_string())?;
+    zip.write_all(text.as_bytes()).map_err(|e| e.to_string())
+}
+
+// 逐行复制日志文件并抹掉密钥，避免把大文件整个读入内存
+fn add_log(zip: &mut Zip, name: &str, path: &Path, secret: Option<&str>) -> Result<(), String> {
+    let file = File::open(path).map_err(|e| format!("读取 {:?} 失败: {}", path, e))?;
+    zip.start_file(name, options()).map_err(|e| e.to_string())?;
+    for line in BufReader::new(file).split(b'\n') {
+        let line = line.map_err(|e| format!("读取 {:?} 失败: {}", path, e))?;
+        let line = String::from_utf8_lossy(&line);
+        let line = match secret {
+            Some(secret) if line.contains(secret) => line.replace(secret, "***").into(),
+            _ => line,
+        };
+        zip.write_all(line.as_bytes())
+            .and_then(|_| zip.write_all(b"\n"))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn environment(app: &AppHandle) -> Value {
+    json!({
+        "generated_at": now_millis(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "app": info::app_info(app.clone(), app.state(), app.state()),
+        "capabilities": capabilities::get_platform_capabilities(app.clone()),
+        "spawn_plan": match backend::spawn_plan(app) {
+            Ok(plan) => json!(plan),
+            Err(e) => json!({ "error": e }),
+        },
+        "backend_pid": app.state::<BackendProcess>().pid(),
+        "last_error": backend::get_last_error(app.state()),
+    })
+}
+
+// 写出诊断包；日志目录中的文件逐个以流式方式写入
+fn write_bundle(
+    dest: &Path,
+    log_dir: &Path,
+    secret: Option<&str>,
+    entries: Vec<(&'static str, Value)>,
+) -> Result<(), String> {
+    let file = File::create(dest).map_err(|e| format!("创建诊断包失败: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    zip.start_file("README.txt", options())
+        .and_then(|_| zip.write_all(README.as_bytes()).map_err(Into::into))
+        .map_err(|e| e.to_string())?;
+    for (name, value) in entries {
+        add_json(&mut zip, name, value)?;
+    }
+
+    let mut log_files: Vec<PathBuf> = std::fs::read_dir(log_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| logs::is_log_file(&entry.file_name().to_string_lossy()))
+                .map(|entry| entry.path())
+                .collect()
+        })
+        .unwrap_or_default();
+    log_files.sort();
+    for path in log_files {
+        let name = format!(
+            "logs/{}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+        if let Err(e) = add_log(&mut zip, &name, &path, secret) {
+            log::warn!("诊断包中略过 {:?}: {}", path, e);
+        }
+    }
+    zip.finish().map_err(|e| format!("写入诊断包失败: {}", e))?;
+    Ok(())
+}
+
+// 一键收集诊断包（日志、隐去凭据的配置、环境信息、崩溃报告和文档库统计）到用户选择的位置，
+// 不包含文档和索引数据。返回诊断包路径，用户取消时返回 None
+#[tauri::command]
+pub async fn create_support_bundle(app: AppHandle) -> Result<Option<String>, String> {
+    let Some(chosen) = app
+        .dialog()
+        .file()
+        .add_filter("zip", &["zip"])
+        .set_file_name(format!("document-qa-support-{}.zip", now_millis()))
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+    let dest: PathBuf = chosen
+        .into_path()
+        .map_err(|e| format!("无效的保存路径: {}", e))?;
+
+    app.state::<BackendLogs>().flush_files();
+    let llm_config = std::fs::read_to_string(credentials::llm_config_path(&app))
+        .ok()
+        .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+        .unwrap_or(Value::Null);
+    let entries = vec![
+        ("config.json", json!(app.state::<ConfigState>().get())),
+        ("llm_config.json", llm_config),
+        ("environment.json", environment(&app)),
+        ("vault.json", json!(vault::vault_stats(&app).await)),
+    ];
+    let log_dir = app.state::<AppPaths>().log_dir.clone();
+    let secret = credentials::saved_api_key(&app);
+
+    let target = dest.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        write_bundle(
+            &target,
+            &log_dir,
+            secret.as_deref().map(String::as_str),
+            entries,
+        )
+    })
+    .await
+    .map_err(|e| format!("生成诊断包失败: {}", e))??;
+    log::info!("诊断包已写入 {:?}", dest);
+    Ok(Some(dest.to_string_lossy().to_string()))
+}
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::http::{self, BackendClient};
+
+// 后端输出任务状态变化时的前缀，后面是任务的 JSON
+const TASK_PREFIX: &str = "[task] ";
+
+// 后端报告的一个后台任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendTask {
+    pub id: String,
+    pub kind: String,
+    pub description: String,
+    pub document_id: Option<i64>,
+    // queued、running，状态更新事件中还可能是 cancelling、completed、failed、cancelled
+    pub status: String,
+    pub cancellable: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackendTasks {
+    // 后端是否提供任务接口（旧版本后端没有）
+    pub supported: bool,
+    pub tasks: Vec<BackendTask>,
+}
+
+// 后端输出的任务状态变化：转发为 backend-task-update 事件
+pub fn capture(app: &AppHandle, message: &str) {
+    let Some(json) = message
+        .find(TASK_PREFIX)
+        .map(|i| &message[i + TASK_PREFIX.len()..])
+    else {
+        return;
+    };
+    match serde_json::from_str::<BackendTask>(json) {
+        Ok(task) => {
+            let _ = app.emit("backend-task-update", &task);
+        }
+        Err(e) => log::debug!("无法解析后端任务状态: {}", e),
+    }
+}
+
+async fn fetch_tasks(client: &BackendClient) -> Result<BackendTasks, String> {
+    let response = http::send(client.get("/api/v1/tasks"), true)
+        .await
+        .map_err(|e| format!("连接后端失败: {}", e))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(BackendTasks {
+            supported: false,
+            tasks: Vec::new(),
+        });
+    }
+    if !status.is_success() {
+        return Err(format!("后端返回错误状态 {}", status));
+    }
+    let tasks = response
+        .json()
+        .await
+        .map_err(|e| format!("解析后端响应失败: {}", e))?;
+    Ok(BackendTasks {
+        supported: true,
+        tasks,
+    })
+}
+
+// 列出后端排队中和正在运行的后台任务；后端没有任务接口时 supported 为 false
+#[tauri::command]
+pub async fn list_backend_tasks(client: State<'_, BackendClient>) -> Result<BackendTasks, String> {
+    fetch_tasks(&client).await
+}
+
+// 取消一个后台任务。只允许取消后端报告为可取消的任务
+#[tauri::command]
+pub async fn cancel_backend_task(
+    client: State<'_, BackendClient>,
+    id: String,
+) -> Result<(), String> {
+    let tasks = fetch_tasks(&client).await?;
+    if !tasks.supported {
+        return Err("当前后端不支持后台任务管理".to_string());
+    }
+    let task = tasks
+        .tasks
+        .iter()
+        .find(|task| task.id == id)
+        .ok_or_else(|| format!("任务 {} 不存在或已结束", id))?;
+    if !task.cancellable {
+        return Err(format!("任务 {} 当前不可取消", id));
+    }
+
+    let path = format!("/api/v1/tasks/{}/cancel", id);
+    let response = http::send(client.post(&path), false)
+        .await
+        .map_err(|e| format!("连接后端失败: {}", e))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body: Value = response.json().await.unwrap_or_default();
+        return Err(format!("取消任务失败 {}: {}", status, body["detail"]));
+    }
+    log::info!("已请求取消后台任务 {} ({})", id, task.description);
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::backend::BackendProcess;
+use crate::sidecar::BackendSource;
+
+// 检查磁盘上后端可执行文件是否被更新的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
+// 打包的后端可执行文件路径（与 shell 插件解析 sidecar 的规则一致：位于主程序同目录）
+pub fn sidecar_path() -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+    let name = if cfg!(windows) {
+        "backend.exe"
+    } else {
+        "backend"
+    };
+    Some(exe_dir.join(name))
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// 记录启动的后端可执行文件（打包的后端或 custom_backend_path）及其修改时间，
+// 用于发现可执行文件更新后仍在运行的旧后端
+pub struct SidecarVersion {
+    spawned: Mutex<Option<(PathBuf, SystemTime)>>,
+    pending: AtomicBool,
+}
+
+impl SidecarVersion {
+    pub fn new() -> Self {
+        Self {
+            spawned: Mutex::new(None),
+            pending: AtomicBool::new(false),
+        }
+    }
+
+    // 后端启动时调用，source 是本次启动使用的后端
+    pub fn record_spawn(&self, source: &BackendSource) {
+        *self.spawned.lock().unwrap() = source.program_path().and_then(|path| {
+            let modified = modified_time(&path)?;
+            Some((path, modified))
+        });
+        self.pending.store(false, Ordering::SeqCst);
+    }
+
+    // 磁盘上的可执行文件是否比正在运行的更新，返回值表示是否是新发现的更新
+    fn check(&self) -> bool {
+        let Some((path, spawned)) = self.spawned.lock().unwrap().clone() else {
+            return false;
+        };
+        let newer = modified_time(&path).is_some_and(|on_disk| on_disk > spawned);
+        newer && !self.pending.swap(true, Ordering::SeqCst)
+    }
+}
+
+// 定期检查后端可执行文件是否被原地更新，发现后发送 backend-update-available
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            if app.state::<BackendProcess>().shutting_down() {
+                break;
+            }
+            if app.state::<SidecarVersion>().check() {
+                log::info!("检测到后端可执行文件已更新，重启后端后生效");
+                let _ = app.emit("backend-update-available", ());
+            }
+        }
+    });
+}
+
+// 正在运行的后端是否是旧版本（需要重启后端以使用新版本）
+#[tauri::command]
+pub fn backend_update_pending(app: AppHandle, version: State<'_, SidecarVersion>) -> bool {
+    if version.check() {
+        let _ = app.emit("backend-update-available", ());
+    }
+    version.pending.load(Ordering::SeqCst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_backend_update_is_detected_once() {
+        let dir = std::env::temp_dir().join(format!("document-qa-update-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("custom-backend");
+        std::fs::write(&path, "v1").unwrap();
+
+        let version = SidecarVersion::new();
+        version.record_spawn(&BackendSource::Custom { path: path.clone() });
+        assert!(!version.check());
+
+        // 原地替换自定义后端：修改时间晚于启动时记录的时间
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+        assert!(version.check());
+        // 同一次更新只通知一次
+        assert!(!version.check());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
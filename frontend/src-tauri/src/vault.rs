@@ -0,0 +1,601 @@
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+use crate::backend::{self, now_millis, BackendProcess};
+use crate::backup;
+use crate::cache;
+use crate::config::{AppConfig, ConfigState};
+use crate::http::{self, BackendClient};
+use crate::index::IndexMaintenance;
+use crate::paths::{dir_size, AppPaths};
+
+// 备份包中的清单文件名
+const MANIFEST_NAME: &str = "manifest.json";
+// 备份包中文档库文件所在的目录前缀
+const VAULT_PREFIX: &str = "vault";
+const MANIFEST_VERSION: u32 = 1;
+// 备份文件名：vault-backup-<毫秒时间戳>.tar.gz
+const BACKUP_PREFIX: &str = "vault-backup-";
+const BACKUP_SUFFIX: &str = ".tar.gz";
+// 文档库目录中可选的配置覆盖文件
+const OVERRIDES_FILE: &str = "vault.toml";
+// vault.toml 可以覆盖的配置项（只包括影响后端的配置）
+const OVERRIDABLE_KEYS: [&str; 6] = [
+    "embedding_batch_size",
+    "ingest_concurrency",
+    "gpu_enabled",
+    "backend_extra_args",
+    "chunk_size",
+    "chunk_overlap",
+];
+
+// 备份清单，作为备份包的第一个条目写入，恢复前用于校验
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub version: u32,
+    // Unix 时间戳（毫秒）
+    pub created_at: u64,
+    pub file_count: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackupResult {
+    pub archive_path: String,
+    pub manifest: BackupManifest,
+}
+
+// 递归列出目录下的所有文件（相对路径和大小）
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<(PathBuf, u64)>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let meta = entry.metadata()?;
+        if meta.is_dir() {
+            collect_files(root, &entry.path(), files)?;
+        } else if meta.is_file() {
+            let relative = entry.path().strip_prefix(root).unwrap().to_path_buf();
+            files.push((relative, meta.len()));
+        }
+    }
+    Ok(())
+}
+
+fn write_archive(vault_dir: &Path, archive_path: &Path) -> Result<BackupManifest, String> {
+    let mut files = Vec::new();
+    collect_files(vault_dir, vault_dir, &mut files)
+        .map_err(|e| format!("读取文档库失败: {}", e))?;
+    let manifest = BackupManifest {
+        version: MANIFEST_VERSION,
+        created_at: now_millis(),
+        file_count: files.len() as u64,
+        total_bytes: files.iter().map(|(_, size)| size).sum(),
+    };
+
+    let file = File::create(archive_path).map_err(|e| format!("创建备份文件失败: {}", e))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let manifest_json = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, MANIFEST_NAME, manifest_json.as_slice())
+        .map_err(|e| format!("写入备份清单失败: {}", e))?;
+
+    for (relative, _) in &files {
+        builder
+            .append_path_with_name(
+                vault_dir.join(relative),
+                Path::new(VAULT_PREFIX).join(relative),
+            )
+            .map_err(|e| format!("写入 {:?} 失败: {}", relative, e))?;
+    }
+    builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| format!("完成备份文件失败: {}", e))?;
+    Ok(manifest)
+}
+
+// 完整读取一遍备份包：解压过程会校验 gzip 校验和，再比对清单中的文件数和总大小
+fn verify_archive(archive_path: &Path) -> Result<BackupManifest, String> {
+    let file = File::open(archive_path).map_err(|e| format!("打开备份文件失败: {}", e))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let mut manifest: Option<BackupManifest> = None;
+    let mut file_count = 0;
+    let mut total_bytes = 0;
+
+    let entries = archive
+        .entries()
+        .map_err(|e| format!("读取备份文件失败: {}", e))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("备份文件已损坏: {}", e))?;
+        let path = entry.path().map_err(|e| e.to_string())?.into_owned();
+        if path == Path::new(MANIFEST_NAME) {
+            let mut text = String::new();
+            entry
+                .read_to_string(&mut text)
+                .map_err(|e| format!("备份文件已损坏: {}", e))?;
+            manifest =
+                Some(serde_json::from_str(&text).map_err(|e| format!("备份清单无效: {}", e))?);
+            continue;
+        }
+        if !path.starts_with(VAULT_PREFIX) {
+            return Err(format!("备份文件中包含意外的条目: {:?}", path));
+        }
+        if entry.header().entry_type().is_file() {
+            let size = std::io::copy(&mut entry, &mut std::io::sink())
+                .map_err(|e| format!("备份文件已损坏: {}", e))?;
+            file_count += 1;
+            total_bytes += size;
+        }
+    }
+
+    let manifest = manifest.ok_or("备份文件缺少清单")?;
+    if manifest.version != MANIFEST_VERSION {
+        return Err(format!("不支持的备份版本: {}", manifest.version));
+    }
+    if manifest.file_count != file_count || manifest.total_bytes != total_bytes {
+        return Err(format!(
+            "备份文件不完整：清单记录 {} 个文件共 {} 字节，实际 {} 个文件共 {} 字节",
+            manifest.file_count, manifest.total_bytes, file_count, total_bytes
+        ));
+    }
+    Ok(manifest)
+}
+
+// 解压到一个全新的目录，unpack_in 会拒绝跳出目标目录的路径
+fn extract_archive(archive_path: &Path, staging_dir: &Path) -> Result<(), String> {
+    // unpack_in 要求目标目录已经存在
+    std::fs::create_dir_all(staging_dir).map_err(|e| format!("创建恢复目录失败: {}", e))?;
+    let file = File::open(archive_path).map_err(|e| format!("打开备份文件失败: {}", e))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let entries = archive
+        .entries()
+        .map_err(|e| format!("读取备份文件失败: {}", e))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("备份文件已损坏: {}", e))?;
+        entry
+            .unpack_in(staging_dir)
+            .map_err(|e| format!("解压备份文件失败: {}", e))?;
+    }
+    Ok(())
+}
+
+// 在后端停止期间执行文档库操作，保证数据一致；原来在运行的后端在操作结束后重新启动
+pub async fn with_backend_stopped<T, F>(app: &AppHandle, operation: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    let was_running = app.state::<BackendProcess>().pid().is_some();
+    if was_running {
+        // 必须等旧进程真正退出，否则它可能仍在写入文档库
+        if !backend::stop_backend_and_wait(app, backend::STOP_WAIT_TIMEOUT).await {
+            return Err("后端进程未能及时退出，请稍后重试".to_string());
+        }
+    } else if cfg!(debug_assertions) {
+        log::warn!("开发模式：后端不由应用管理，请确保操作期间没有写入文档库");
+    }
+
+    let result = tauri::async_runtime::spawn_blocking(operation)
+        .await
+        .map_err(|e| format!("文档库操作异常退出: {}", e))
+        .and_then(|result| result);
+
+    if was_running {
+        if let Err(e) = backend::spawn_backend(app).await {
+            log::error!("文档库操作后重启后端失败: {}", e);
+        }
+    }
+    result
+}
+
+// 把整个文档库打包为 dest 目录下的 tar.gz 备份，返回备份路径和清单
+#[tauri::command]
+pub async fn backup_vault(
+    app: AppHandle,
+    maintenance: State<'_, IndexMaintenance>,
+    dest: String,
+) -> Result<BackupResult, String> {
+    let dest = PathBuf::from(dest);
+    backup::check_backup_dir(&app.state::<AppPaths>(), &dest)?;
+    let _guard = maintenance.begin("备份")?;
+    create_backup(&app, &dest).await
+}
+
+// 备份文件名中的时间戳（毫秒），不是备份文件时返回 None
+pub fn backup_timestamp(file_name: &str) -> Option<u64> {
+    file_name
+        .strip_prefix(BACKUP_PREFIX)?
+        .strip_suffix(BACKUP_SUFFIX)?
+        .parse()
+        .ok()
+}
+
+// 备份文档库到 dest_dir，调用方负责持有维护任务锁
+pub async fn create_backup(app: &AppHandle, dest_dir: &Path) -> Result<BackupResult, String> {
+    std::fs::create_dir_all(dest_dir).map_err(|e| format!("创建备份目录失败: {}", e))?;
+    let archive_path = dest_dir.join(format!(
+        "{}{}{}",
+        BACKUP_PREFIX,
+        now_millis(),
+        BACKUP_SUFFIX
+    ));
+    let vault_dir = app.state::<AppPaths>().vault_dir();
+
+    log::info!("开始备份文档库到 {:?}", archive_path);
+    let target = archive_path.clone();
+    let manifest = with_backend_stopped(app, move || {
+        let result = write_archive(&vault_dir, &target);
+        if result.is_err() {
+            let _ = std::fs::remove_file(&target);
+        }
+        result
+    })
+    .await?;
+    log::info!(
+        "文档库备份完成：{} 个文件，共 {} 字节",
+        manifest.file_count,
+        manifest.total_bytes
+    );
+
+    Ok(BackupResult {
+        archive_path: archive_path.to_string_lossy().to_string(),
+        manifest,
+    })
+}
+
+// 把备份解压到 staging_dir，再用其中的文档库替换 vault_dir（原文档库移到 previous_dir）。
+// 替换失败时把原文档库移回原处，不会留下没有文档库的状态
+fn replace_vault(
+    archive_path: &Path,
+    staging_dir: &Path,
+    vault_dir: &Path,
+    previous_dir: &Path,
+) -> Result<(), String> {
+    if let Err(e) = extract_archive(archive_path, staging_dir) {
+        let _ = std::fs::remove_dir_all(staging_dir);
+        return Err(e);
+    }
+    // 空文档库的备份只有清单，解压后没有文档库目录
+    let restored = staging_dir.join(VAULT_PREFIX);
+    if let Err(e) = std::fs::create_dir_all(&restored) {
+        let _ = std::fs::remove_dir_all(staging_dir);
+        return Err(format!("创建恢复目录失败: {}", e));
+    }
+    let moved = vault_dir.exists();
+    if moved {
+        if let Err(e) = std::fs::rename(vault_dir, previous_dir) {
+            let _ = std::fs::remove_dir_all(staging_dir);
+            return Err(format!("移动现有文档库失败: {}", e));
+        }
+    }
+    if let Err(e) = std::fs::rename(&restored, vault_dir) {
+        if moved {
+            if let Err(rollback) = std::fs::rename(previous_dir, vault_dir) {
+                log::error!(
+                    "还原原文档库失败，原文档库保留在 {:?}: {}",
+                    previous_dir,
+                    rollback
+                );
+            }
+        }
+        let _ = std::fs::remove_dir_all(staging_dir);
+        return Err(format!("替换文档库失败: {}", e));
+    }
+    let _ = std::fs::remove_dir_all(staging_dir);
+    log::info!("文档库已恢复，原文档库保留在 {:?}", previous_dir);
+    Ok(())
+}
+
+// 从备份恢复文档库：先完整校验备份包，再解压到新目录，
+// 校验和解压都成功后才替换现有文档库（原文档库重命名保留）
+#[tauri::command]
+pub async fn restore_vault(
+    app: AppHandle,
+    paths: State<'_, AppPaths>,
+    maintenance: State<'_, IndexMaintenance>,
+    archive: String,
+) -> Result<BackupManifest, String> {
+    let _guard = maintenance.begin("恢复")?;
+    let archive_path = PathBuf::from(archive);
+    let checked = archive_path.clone();
+    let manifest = tauri::async_runtime::spawn_blocking(move || verify_archive(&checked))
+        .await
+        .map_err(|e| format!("校验备份异常退出: {}", e))??;
+    log::info!("备份校验通过：{} 个文件", manifest.file_count);
+
+    let vault_dir = paths.vault_dir();
+    // 临时目录与文档库放在同一父目录下，保证可以直接重命名
+    let parent = vault_dir.parent().unwrap_or(&paths.data_dir).to_path_buf();
+    let stamp = now_millis();
+    let staging_dir = parent.join(format!("restore-{}", stamp));
+    let vault_name = vault_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "data".to_string());
+    let previous_dir = parent.join(format!("{}.before-restore-{}", vault_name, stamp));
+
+    with_backend_stopped(&app, move || {
+        replace_vault(&archive_path, &staging_dir, &vault_dir, &previous_dir)
+    })
+    .await?;
+
+    Ok(manifest)
+}
+
+// 让后端把数据库、向量索引、上传文件和 LLM 配置保存到指定文档库的环境变量
+pub fn storage_env(vault: &Path) -> Vec<(String, String)> {
+    vec![
+        ("DATA_DIR".to_string(), vault.to_string_lossy().to_string()),
+        (
+            "DATABASE_URL".to_string(),
+            format!("sqlite:///{}", vault.join("app.db").to_string_lossy()),
+        ),
+        (
+            "CHROMA_PERSIST_DIRECTORY".to_string(),
+            vault.join("chroma").to_string_lossy().to_string(),
+        ),
+        (
+            "UPLOAD_DIR".to_string(),
+            vault.join("uploads").to_string_lossy().to_string(),
+        ),
+    ]
+}
+
+// 读取文档库的 vault.toml，文件不存在时返回空表；未知的配置项忽略并警告
+fn load_overrides(vault_dir: &Path) -> Result<toml::Table, String> {
+    let path = vault_dir.join(OVERRIDES_FILE);
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(toml::Table::new()),
+        Err(e) => return Err(format!("读取 {:?} 失败: {}", path, e)),
+    };
+    let mut table: toml::Table = text
+        .parse()
+        .map_err(|e| format!("解析 {:?} 失败: {}", path, e))?;
+    table.retain(|key, _| {
+        let known = OVERRIDABLE_KEYS.contains(&key);
+        if !known {
+            log::warn!("{:?} 中的配置项 {} 不支持按文档库覆盖，已忽略", path, key);
+        }
+        known
+    });
+    Ok(table)
+}
+
+// 把文档库的覆盖项叠加到全局配置上（覆盖项优先），结果需通过配置校验
+fn merge_overrides(config: &AppConfig, overrides: &toml::Table) -> Result<AppConfig, String> {
+    let mut merged = serde_json::to_value(config).map_err(|e| e.to_string())?;
+    let overrides = serde_json::to_value(overrides).map_err(|e| e.to_string())?;
+    if let (Some(merged), Some(overrides)) = (merged.as_object_mut(), overrides.as_object()) {
+        merged.extend(overrides.clone());
+    }
+    let merged: AppConfig =
+        serde_json::from_value(merged).map_err(|e| format!("vault.toml 中的配置无效: {}", e))?;
+    merged.validate()?;
+    Ok(merged)
+}
+
+// 当前生效的配置：全局配置叠加当前文档库的 vault.toml
+pub fn effective_config(app: &AppHandle) -> Result<AppConfig, String> {
+    let config = app.state::<ConfigState>().get();
+    let overrides = load_overrides(&app.state::<AppPaths>().vault_dir())?;
+    merge_overrides(&config, &overrides)
+}
+
+#[derive(Debug, Serialize)]
+pub struct VaultSwitch {
+    pub vault: String,
+    // 该文档库 vault.toml 覆盖的配置项
+    pub overrides: Vec<String>,
+}
+
+// 切换到指定的文档库目录并按叠加了 vault.toml 的配置重启后端。
+// 覆盖文件无效时不切换
+#[tauri::command]
+pub async fn switch_vault(
+    app: AppHandle,
+    paths: State<'_, AppPaths>,
+    config: State<'_, ConfigState>,
+    maintenance: State<'_, IndexMaintenance>,
+    path: String,
+) -> Result<VaultSwitch, String> {
+    let _guard = maintenance.begin("切换文档库")?;
+    let vault_dir = PathBuf::from(&path);
+    std::fs::create_dir_all(&vault_dir).map_err(|e| format!("创建文档库目录失败: {}", e))?;
+    let overrides = load_overrides(&vault_dir)?;
+    merge_overrides(&config.get(), &overrides)?;
+
+    // 选择默认文档库时清除记录，而不是保存一个绝对路径
+    let active = (vault_dir != paths.default_vault_dir()).then(|| vault_dir.clone());
+    let mut next = config.get();
+    next.active_vault = active.clone();
+    config.set(next)?;
+    paths.set_active_vault(active);
+    let overrides: Vec<String> = overrides.keys().cloned().collect();
+    log::info!("已切换到文档库 {:?}，覆盖项: {:?}", vault_dir, overrides);
+
+    backend::restart_backend(&app).await?;
+    Ok(VaultSwitch {
+        vault: vault_dir.to_string_lossy().to_string(),
+        overrides,
+    })
+}
+
+// 文档库统计：位置、占用空间与上限、缓存大小和（后端在线时）文档数量
+#[derive(Debug, Serialize)]
+pub struct VaultStats {
+    pub vault_dir: PathBuf,
+    pub active_vault: Option<PathBuf>,
+    pub total_bytes: u64,
+    pub index_bytes: u64,
+    pub cache_dir: PathBuf,
+    pub cache_bytes: u64,
+    // 文档库大小上限（未设置为 None）
+    pub max_vault_bytes: Option<u64>,
+    pub document_count: Option<usize>,
+}
+
+pub async fn vault_stats(app: &AppHandle) -> VaultStats {
+    let paths = app.state::<AppPaths>();
+    let vault = paths.vault_dir();
+    let cache_dir = cache::cache_dir(&paths, &app.state::<ConfigState>().get());
+    let request = app
+        .state::<BackendClient>()
+        .get("/api/v1/documents?limit=10000")
+        .timeout(Duration::from_secs(5));
+    let document_count = match http::send(request, true).await {
+        Ok(response) => response
+            .json::<Vec<serde_json::Value>>()
+            .await
+            .ok()
+            .map(|docs| docs.len()),
+        Err(_) => None,
+    };
+
+    let index_dir = paths.index_dir();
+    let (total_bytes, index_bytes, cache_bytes) = {
+        let vault = vault.clone();
+        let cache_dir = cache_dir.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            (dir_size(&vault), dir_size(&index_dir), dir_size(&cache_dir))
+        })
+        .await
+        .unwrap_or_default()
+    };
+    VaultStats {
+        vault_dir: vault,
+        active_vault: paths.active_vault(),
+        total_bytes,
+        index_bytes,
+        cache_dir,
+        cache_bytes,
+        max_vault_bytes: app.state::<ConfigState>().get().max_vault_bytes,
+        document_count,
+    }
+}
+
+#[tauri::command]
+pub async fn get_vault_stats(app: AppHandle) -> VaultStats {
+    vault_stats(&app).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 在系统临时目录下创建一个本测试专用的文档库目录，写入 vault.toml
+    fn vault_with_overrides(name: &str, text: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("document-qa-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(OVERRIDES_FILE), text).unwrap();
+        dir
+    }
+
+    #[test]
+    fn vault_overrides_take_precedence_over_global_config() {
+        let config = AppConfig {
+            chunk_size: 1200,
+            gpu_enabled: false,
+            embedding_batch_size: 16,
+            ..AppConfig::default()
+        };
+        let overrides: toml::Table = "chunk_size = 600\ngpu_enabled = true".parse().unwrap();
+        let merged = merge_overrides(&config, &overrides).unwrap();
+        assert_eq!(merged.chunk_size, 600);
+        assert!(merged.gpu_enabled);
+        // 没有覆盖的配置项保持全局配置的值
+        assert_eq!(merged.embedding_batch_size, 16);
+        assert_eq!(merged.chunk_overlap, config.chunk_overlap);
+    }
+
+    #[test]
+    fn invalid_merged_config_is_rejected() {
+        let config = AppConfig::default();
+        let overrides: toml::Table = format!("chunk_overlap = {}", config.chunk_size)
+            .parse()
+            .unwrap();
+        assert!(merge_overrides(&config, &overrides).is_err());
+    }
+
+    #[test]
+    fn unknown_override_keys_are_ignored() {
+        let dir = vault_with_overrides(
+            "vault-overrides",
+            "chunk_size = 600\nlog_dir = \"/tmp/elsewhere\"\nmax_concurrent_requests = 1\n",
+        );
+        let overrides = load_overrides(&dir).unwrap();
+        assert_eq!(overrides.keys().collect::<Vec<_>>(), vec!["chunk_size"]);
+
+        let config = AppConfig::default();
+        let merged = merge_overrides(&config, &overrides).unwrap();
+        assert_eq!(merged.log_dir, config.log_dir);
+        assert_eq!(
+            merged.max_concurrent_requests,
+            config.max_concurrent_requests
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn restore_empty_vault_backup_replaces_vault() {
+        let root = std::env::temp_dir().join(format!("document-qa-restore-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let empty = root.join("empty");
+        let vault_dir = root.join("data");
+        std::fs::create_dir_all(&empty).unwrap();
+        std::fs::create_dir_all(&vault_dir).unwrap();
+        std::fs::write(vault_dir.join("app.db"), "current").unwrap();
+
+        let archive = root.join("backup.tar.gz");
+        let manifest = write_archive(&empty, &archive).unwrap();
+        assert_eq!(manifest.file_count, 0);
+        verify_archive(&archive).unwrap();
+
+        let previous_dir = root.join("data.before-restore");
+        replace_vault(&archive, &root.join("staging"), &vault_dir, &previous_dir).unwrap();
+        assert!(vault_dir.is_dir());
+        assert_eq!(std::fs::read_dir(&vault_dir).unwrap().count(), 0);
+        assert!(previous_dir.join("app.db").exists());
+        assert!(!root.join("staging").exists());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn restore_backup_brings_back_vault_files() {
+        let root =
+            std::env::temp_dir().join(format!("document-qa-roundtrip-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let vault_dir = root.join("data");
+        std::fs::create_dir_all(vault_dir.join("uploads")).unwrap();
+        std::fs::write(vault_dir.join("uploads").join("a.pdf"), "backed up").unwrap();
+        let archive = root.join("backup.tar.gz");
+        write_archive(&vault_dir, &archive).unwrap();
+
+        std::fs::write(vault_dir.join("uploads").join("a.pdf"), "changed").unwrap();
+        let previous_dir = root.join("data.before-restore");
+        replace_vault(&archive, &root.join("staging"), &vault_dir, &previous_dir).unwrap();
+        let restored = std::fs::read_to_string(vault_dir.join("uploads").join("a.pdf")).unwrap();
+        assert_eq!(restored, "backed up");
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn missing_overrides_file_is_empty() {
+        let dir = vault_with_overrides("vault-no-overrides", "");
+        std::fs::remove_file(dir.join(OVERRIDES_FILE)).unwrap();
+        assert!(load_overrides(&dir).unwrap().is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,71 @@
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::State;
+
+use crate::http::{self, BackendClient};
+
+// 后端模型预热状态，后端每次重新启动后需要重新预热
+pub struct Warmup {
+    warmed: AtomicBool,
+    // 保证同一时间只有一个预热请求，其余调用等待其完成
+    running: tokio::sync::Mutex<()>,
+}
+
+impl Warmup {
+    pub fn new() -> Self {
+        Self {
+            warmed: AtomicBool::new(false),
+            running: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn reset(&self) {
+        self.warmed.store(false, Ordering::SeqCst);
+    }
+}
+
+// 在第一次提问前预先加载模型，已预热过时立即返回
+#[tauri::command]
+pub async fn prewarm_backend(
+    client: State<'_, BackendClient>,
+    warmup: State<'_, Warmup>,
+) -> Result<(), String> {
+    if warmup.warmed.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+    let _running = warmup.running.lock().await;
+    if warmup.warmed.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    log::info!("正在预热后端模型...");
+    let response = http::send(client.post("/api/v1/warmup"), true)
+        .await
+        .map_err(|e| format!("连接后端失败: {}", e))?;
+    // 旧版本后端没有预热接口，退而只向量化一条文本来加载嵌入模型（不调用 LLM、不写入问答历史）
+    let response = if response.status() == reqwest::StatusCode::NOT_FOUND {
+        http::send(
+            client
+                .post("/api/v1/settings/embedding/benchmark")
+                .json(&json!({ "texts": ["warmup"] })),
+            true,
+        )
+        .await
+        .map_err(|e| format!("连接后端失败: {}", e))?
+    } else {
+        response
+    };
+    // 更早的后端两个接口都没有，模型会在第一次提问时加载
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        log::info!("后端不支持预热，跳过");
+        warmup.warmed.store(true, Ordering::SeqCst);
+        return Ok(());
+    }
+    if !response.status().is_success() {
+        return Err(format!("预热失败: {}", response.status()));
+    }
+
+    warmup.warmed.store(true, Ordering::SeqCst);
+    log::info!("后端模型预热完成");
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+use sysinfo::{MemoryRefreshKind, Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::OwnedSemaphorePermit;
+
+use crate::backend::{self, now_millis, BackendProcess};
+use crate::config::ConfigState;
+use crate::http::{self, BackendClient};
+use crate::requests::RequestLimiter;
+
+// 内存采样间隔
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+// 连续超过阈值多少次才触发重启，避免短暂的峰值导致误重启
+const SUSTAINED_SAMPLES: u32 = 3;
+// 系统可用内存的采样间隔
+const MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+// 可用内存回到阈值的 1.25 倍以上才算恢复，避免在阈值附近反复切换
+const RECOVERY_FACTOR: f64 = 1.25;
+// 保留的内存采样数（按 SAMPLE_INTERVAL 约 5 分钟），后端因内存不足被终止时写入崩溃报告
+const MEMORY_HISTORY_LEN: usize = 30;
+
+// 一次内存采样：后端常驻内存和系统可用内存
+#[derive(Debug, Clone, Serialize)]
+pub struct MemorySample {
+    // Unix 时间戳（毫秒）
+    pub timestamp: u64,
+    pub rss_mb: u64,
+    pub available_mb: u64,
+}
+
+// 最近的内存采样（最早的在前）
+#[derive(Default)]
+pub struct MemoryHistory(Mutex<VecDeque<MemorySample>>);
+
+impl MemoryHistory {
+    fn push(&self, sample: MemorySample) {
+        let mut samples = self.0.lock().unwrap();
+        if samples.len() == MEMORY_HISTORY_LEN {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    pub fn snapshot(&self) -> Vec<MemorySample> {
+        self.0.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+// 读取指定进程的常驻内存（MB），进程已不存在时返回 None
+fn sample_rss_mb(system: &mut System, pid: u32) -> Option<u64> {
+    let pid = Pid::from_u32(pid);
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::nothing().with_memory(),
+    );
+    system.process(pid).map(|p| p.memory() / 1024 / 1024)
+}
+
+// 后台监控后端内存占用并记录采样，持续超过 backend_max_rss_mb 时重启后端
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut system = System::new();
+        let mut over_limit = 0;
+        loop {
+            tokio::time::sleep(SAMPLE_INTERVAL).await;
+            if app.state::<BackendProcess>().shutting_down() {
+                break;
+            }
+
+            let Some(pid) = app.state::<BackendProcess>().pid() else {
+                over_limit = 0;
+                continue;
+            };
+            // 两次采样之间进程可能已经退出，此时重新计数
+            let Some(rss_mb) = sample_rss_mb(&mut system, pid) else {
+                over_limit = 0;
+                continue;
+            };
+            app.state::<MemoryHistory>().push(MemorySample {
+                timestamp: now_millis(),
+                rss_mb,
+                available_mb: available_memory_mb(&mut system),
+            });
+            let Some(limit_mb) = app.state::<ConfigState>().get().backend_max_rss_mb else {
+                over_limit = 0;
+                continue;
+            };
+
+            if rss_mb <= limit_mb {
+                over_limit = 0;
+                continue;
+            }
+            over_limit += 1;
+            log::warn!(
+                "后端内存占用 {} MB 超过阈值 {} MB（{}/{}）",
+                rss_mb,
+                limit_mb,
+                over_limit,
+                SUSTAINED_SAMPLES
+            );
+            if over_limit < SUSTAINED_SAMPLES {
+                continue;
+            }
+
+            over_limit = 0;
+            let _ = app.emit(
+                "backend-oom-restart",
+                serde_json::json!({ "rss_mb": rss_mb, "limit_mb": limit_mb }),
+            );
+            if let Err(e) = backend::restart_backend(&app).await {
+                log::error!("内存超限后重启后端失败: {}", e);
+            }
+        }
+    });
+}
+
+// 系统当前可用内存（MB）
+fn available_memory_mb(system: &mut System) -> u64 {
+    system.refresh_memory_specifics(MemoryRefreshKind::nothing().with_ram());
+    system.available_memory() / 1024 / 1024
+}
+
+// 后端是否有文档正在等待或正在建立索引；请求失败时视为没有
+pub async fn is_indexing(client: &BackendClient) -> bool {
+    let request = client.get("/api/v1/documents?limit=1000");
+    let Ok(response) = http::send(request, true).await else {
+        return false;
+    };
+    let Ok(documents) = response.json::<Vec<Value>>().await else {
+        return false;
+    };
+    documents
+        .iter()
+        .any(|doc| matches!(doc["status"].as_str(), Some("pending" | "processing")))
+}
+
+// 索引期间监控系统可用内存：低于 low_memory_threshold_mb 时发送 low-memory 事件，
+// 并按配置临时把发往后端的并发请求降为 1；内存恢复或索引结束后还原并发送 low-memory-cleared
+pub fn start_memory_monitor(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut system = System::new();
+        let mut low = false;
+        let mut throttle: Option<OwnedSemaphorePermit> = None;
+        loop {
+            tokio::time::sleep(MEMORY_SAMPLE_INTERVAL).await;
+            if app.state::<BackendProcess>().shutting_down() {
+                break;
+            }
+
+            let config = app.state::<ConfigState>().get();
+            let threshold_mb = config.low_memory_threshold_mb;
+            let available_mb = available_memory_mb(&mut system);
+            let recovered =
+                threshold_mb == 0 || available_mb as f64 >= threshold_mb as f64 * RECOVERY_FACTOR;
+
+            if low {
+                if recovered || !is_indexing(&app.state::<BackendClient>()).await {
+                    low = false;
+                    throttle = None;
+                    log::info!("系统可用内存已恢复: {} MB", available_mb);
+                    let _ = app.emit(
+                        "low-memory-cleared",
+                        serde_json::json!({ "available_mb": available_mb }),
+                    );
+                } else if config.throttle_on_low_memory && throttle.is_none() {
+                    // 进入低内存状态时名额可能正被占用，之后的采样中继续尝试
+                    throttle = app.state::<RequestLimiter>().throttle();
+                }
+                continue;
+            }
+
+            if threshold_mb == 0
+                || available_mb >= threshold_mb
+                || !is_indexing(&app.state::<BackendClient>()).await
+            {
+                continue;
+            }
+            low = true;
+            if config.throttle_on_low_memory {
+                throttle = app.state::<RequestLimiter>().throttle();
+            }
+            log::warn!(
+                "索引期间系统可用内存不足: {} MB（阈值 {} MB）",
+                available_mb,
+                threshold_mb
+            );
+            let _ = app.emit(
+                "low-memory",
+                serde_json::json!({
+                    "available_mb": available_mb,
+                    "threshold_mb": threshold_mb,
+                    "throttled": config.throttle_on_low_memory,
+                }),
+            );
+        }
+    });
+}
@@ -0,0 +1,134 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tauri::{AppHandle, Listener, Manager, State};
+
+use crate::backend::now_millis;
+use crate::config::ConfigState;
+
+// 可以触发 webhook 的事件
+pub const SUPPORTED_EVENTS: [&str; 7] = [
+    "backend-ready",
+    "backend-failed",
+    "indexing-done",
+    "backend-oom-restart",
+    "backend-integrity-failed",
+    "low-memory",
+    "index-stale",
+];
+// 默认订阅的事件
+pub const DEFAULT_EVENTS: [&str; 3] = ["backend-ready", "backend-failed", "indexing-done"];
+// 单次请求超时
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+// 最多尝试次数（含首次）和重试间隔
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+pub fn validate_url(url: &str) -> Result<(), String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("无效的 webhook 地址 {}: {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("webhook 地址只支持 http 或 https: {}", url));
+    }
+    Ok(())
+}
+
+pub fn validate_events(events: &[String]) -> Result<(), String> {
+    match events
+        .iter()
+        .find(|event| !SUPPORTED_EVENTS.contains(&event.as_str()))
+    {
+        Some(event) => Err(format!("不支持的 webhook 事件: {}", event)),
+        None => Ok(()),
+    }
+}
+
+// 发送 webhook 用的 HTTP 客户端
+pub struct Webhook {
+    client: reqwest::Client,
+}
+
+impl Webhook {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    // POST 通知，连接失败或服务端返回 5xx 时重试，返回最终的状态码
+    async fn post(&self, url: &str, body: &Value) -> Result<u16, String> {
+        let mut last_error = String::new();
+        for attempt in 1..=MAX_ATTEMPTS {
+            match self.client.post(url).json(body).send().await {
+                Ok(response) if !response.status().is_server_error() => {
+                    return Ok(response.status().as_u16());
+                }
+                Ok(response) => last_error = format!("服务端返回 {}", response.status()),
+                Err(e) => last_error = e.to_string(),
+            }
+            if attempt < MAX_ATTEMPTS {
+                tokio::time::sleep(RETRY_DELAY).await;
+            }
+        }
+        Err(format!("发送 webhook 失败: {}", last_error))
+    }
+}
+
+fn payload(event: &str, data: Value) -> Value {
+    json!({
+        "event": event,
+        "timestamp": now_millis(),
+        "data": data,
+    })
+}
+
+// 订阅所有支持的事件；每次触发时读取最新配置，未设置地址或未订阅该事件时忽略
+pub fn start(app: &AppHandle) {
+    for event in SUPPORTED_EVENTS {
+        let handle = app.clone();
+        app.listen(event, move |e| {
+            let config = handle.state::<ConfigState>().get();
+            let Some(url) = config.webhook_url else {
+                return;
+            };
+            if !config.webhook_events.iter().any(|name| name == event) {
+                return;
+            }
+            let data = serde_json::from_str(e.payload()).unwrap_or(Value::Null);
+            let handle = handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let body = payload(event, data);
+                if let Err(e) = handle.state::<Webhook>().post(&url, &body).await {
+                    log::warn!("{} 事件的 webhook 通知失败: {}", event, e);
+                }
+            });
+        });
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookTest {
+    pub url: String,
+    pub status: u16,
+}
+
+// 向 webhook 地址（默认使用配置中的地址）发送一条示例通知
+#[tauri::command]
+pub async fn test_webhook(
+    config: State<'_, ConfigState>,
+    webhook: State<'_, Webhook>,
+    url: Option<String>,
+) -> Result<WebhookTest, String> {
+    let url = url
+        .or_else(|| config.get().webhook_url)
+        .ok_or("尚未配置 webhook 地址")?;
+    validate_url(&url)?;
+    let body = payload(
+        "test",
+        json!({ "message": "这是一条来自 Document-QA 的测试通知" }),
+    );
+    let status = webhook.post(&url, &body).await?;
+    Ok(WebhookTest { url, status })
+}